@@ -1,59 +1,68 @@
-advent_of_code::solution!(1);
+use std::collections::HashMap;
 
-pub fn part_one(input: &str) -> Option<u64> {
-    let mut arr1: Vec<u64> = vec![];
-    let mut arr2: Vec<u64> = vec![];
+use advent_of_code::template::parse::{InputError, ParseExt};
+use advent_of_code::template::{solution::Solution, Day};
 
-    let lines = input.lines();
-    for line in lines {
-        let (a, b) = line.split_once("   ").unwrap();
-        let a: u64 = a.parse().unwrap();
-        let b: u64 = b.parse().unwrap();
+advent_of_code::solution!(solution = HistorianHysteria);
 
-        arr1.push(a);
-        arr2.push(b);
-    }
+pub struct HistorianHysteria;
 
-    arr1.sort();
-    arr2.sort();
+/// The left and right list of location IDs.
+pub struct Lists {
+    left: Vec<u64>,
+    right: Vec<u64>,
+}
 
-    let mut sum = 0;
-    for (a, b) in arr1.iter().zip(arr2.iter()) {
-        sum += a.abs_diff(*b);
-    }
+impl Solution for HistorianHysteria {
+    const DAY: Day = advent_of_code::day!(1);
+    const TITLE: Option<&'static str> = Some("Historian Hysteria");
 
-    Some(sum)
-}
+    type Input<'a> = Lists;
+    type Answer1 = Option<u64>;
+    type Answer2 = Option<u64>;
 
-pub fn part_two(input: &str) -> Option<u64> {
-    let mut arr1: Vec<u64> = vec![];
-    let mut arr2: Vec<u64> = vec![];
+    fn parse(&self, input: &str) -> Result<Lists, InputError> {
+        let mut left: Vec<u64> = vec![];
+        let mut right: Vec<u64> = vec![];
 
-    let lines = input.lines();
-    for line in lines {
-        let (a, b) = line.split_once("   ").unwrap();
-        let a: u64 = a.parse().unwrap();
-        let b: u64 = b.parse().unwrap();
+        for line in input.lines() {
+            let (a, b) = line.try_split_once("   ")?;
+            left.push(a.try_parse()?);
+            right.push(b.try_parse()?);
+        }
 
-        arr1.push(a);
-        arr2.push(b);
+        Ok(Lists { left, right })
     }
 
-    let mut freq = std::collections::HashMap::new();
-    for &b in arr2.iter() {
-        *freq.entry(b).or_insert(0) += 1;
+    fn part_one(&self, lists: &Lists) -> Option<u64> {
+        let mut left = lists.left.clone();
+        let mut right = lists.right.clone();
+        left.sort();
+        right.sort();
+
+        let mut sum = 0;
+        for (a, b) in left.iter().zip(right.iter()) {
+            sum += a.abs_diff(*b);
+        }
+
+        Some(sum)
     }
 
-    let mut result: u64 = 0;
-    for x in arr1.iter() {
-        if let Some(y) = freq.get(x) {
-            if *y > 0 {
+    fn part_two(&self, lists: &Lists) -> Option<u64> {
+        let mut freq = HashMap::new();
+        for &b in lists.right.iter() {
+            *freq.entry(b).or_insert(0) += 1;
+        }
+
+        let mut result: u64 = 0;
+        for x in lists.left.iter() {
+            if let Some(y) = freq.get(x) {
                 result += x * y;
             }
         }
-    }
 
-    Some(result)
+        Some(result)
+    }
 }
 
 #[cfg(test)]
@@ -62,13 +71,21 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(11));
+        let input = advent_of_code::template::read_file("examples", DAY);
+        let lists = HistorianHysteria.parse(&input).unwrap();
+        assert_eq!(HistorianHysteria.part_one(&lists), Some(11));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(31));
+        let input = advent_of_code::template::read_file("examples", DAY);
+        let lists = HistorianHysteria.parse(&input).unwrap();
+        assert_eq!(HistorianHysteria.part_two(&lists), Some(31));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let error = HistorianHysteria.parse("3   4\n4 3\n").err().unwrap();
+        assert_eq!(error.message(), "expected `   ` in `4 3`");
     }
 }
@@ -7,7 +7,7 @@ fn check_words(words: Vec<char>, target: &str) -> bool {
     words == target || reverse_words == target
 }
 
-fn scan_cell(grid: &Vec<Vec<char>>, x: usize, y: usize, m: usize, n: usize) -> u64 {
+fn scan_cell(grid: &[Vec<char>], x: usize, y: usize, m: usize, n: usize) -> u64 {
     let mut result: u64 = 0;
 
     // horizontal
@@ -65,20 +65,22 @@ fn scan_cell(grid: &Vec<Vec<char>>, x: usize, y: usize, m: usize, n: usize) -> u
     result
 }
 
-fn scan_cell_2(grid: &Vec<Vec<char>>, x: usize, y: usize, m: usize, n: usize) -> u64 {
+fn scan_cell_2(grid: &[Vec<char>], x: usize, y: usize, m: usize, n: usize) -> u64 {
     let mut result: u64 = 0;
 
     // diagonal
-    if x + 2 < n && y + 2 < m {
-        if check_words(
+    if x + 2 < n
+        && y + 2 < m
+        && check_words(
             vec![grid[y][x], grid[y + 1][x + 1], grid[y + 2][x + 2]],
             "MAS",
-        ) && check_words(
+        )
+        && check_words(
             vec![grid[y + 2][x], grid[y + 1][x + 1], grid[y][x + 2]],
             "MAS",
-        ) {
-            result += 1;
-        }
+        )
+    {
+        result += 1;
     }
 
     result
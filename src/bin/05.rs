@@ -1,6 +1,56 @@
-use std::{cmp::Ordering, collections::HashSet};
+use std::{
+    cmp::Ordering,
+    collections::{HashMap, HashSet},
+};
 
-advent_of_code::solution!(5);
+use advent_of_code::template::generate::{Generator, Rng};
+
+advent_of_code::solution!(5, generator = PrintQueue);
+
+/// Generates a rule for every pair of 49 pages, followed by `size` updates.
+struct PrintQueue;
+
+impl Generator for PrintQueue {
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        let mut pages: Vec<u64> = (10..100).collect();
+        rng.shuffle(&mut pages);
+        pages.truncate(49);
+
+        // pages are ordered by their position in `pages`.
+        let position: HashMap<u64, usize> =
+            pages.iter().enumerate().map(|(i, p)| (*p, i)).collect();
+
+        let mut rules = vec![];
+        for (i, x) in pages.iter().enumerate() {
+            for y in &pages[i + 1..] {
+                rules.push(format!("{x}|{y}"));
+            }
+        }
+        rng.shuffle(&mut rules);
+
+        let updates: Vec<String> = (0..size)
+            .map(|_| {
+                let mut update = pages.clone();
+                rng.shuffle(&mut update);
+                update.truncate(5 + 2 * rng.below(10));
+                if rng.chance(0.5) {
+                    update.sort_by_key(|p| position[p]);
+                }
+                update
+                    .iter()
+                    .map(u64::to_string)
+                    .collect::<Vec<_>>()
+                    .join(",")
+            })
+            .collect();
+
+        format!("{}\n\n{}\n", rules.join("\n"), updates.join("\n"))
+    }
+
+    fn default_size(&self) -> usize {
+        200
+    }
+}
 
 pub fn part_one(input: &str) -> Option<u64> {
     let (orderings, updates) = input.split_once("\n\n").unwrap();
@@ -31,12 +31,7 @@ impl Direction {
 
 impl Clone for Direction {
     fn clone(&self) -> Self {
-        match self {
-            Direction::Up => Direction::Up,
-            Direction::Down => Direction::Down,
-            Direction::Left => Direction::Left,
-            Direction::Right => Direction::Right,
-        }
+        *self
     }
 }
 
@@ -80,13 +75,13 @@ impl Grid {
             }
             grid.push(row);
         }
-        return Self {
+        Self {
             width: grid[0].len(),
             height: grid.len(),
             cells: grid,
             init_position: position,
-            init_direction: direction.clone(),
-        };
+            init_direction: direction,
+        }
     }
 
     fn get_next_move(
@@ -95,7 +90,7 @@ impl Grid {
         direction: &Direction,
     ) -> ((isize, isize), Direction) {
         let mut next_position = direction.get_next_position(position);
-        let mut next_direction = direction.clone();
+        let mut next_direction = *direction;
         while self.is_valid_position(&next_position)
             && self.cells[next_position.1 as usize][next_position.0 as usize] == '#'
         {
@@ -130,14 +125,14 @@ impl Grid {
             return false;
         }
         let (position, direction) = self.get_next_move(start_position, start_direction);
-        let new_route = (position, direction.clone());
+        let new_route = (position, direction);
         if routes.contains(&new_route) {
-            return true;
+            true
         } else {
             routes.insert(new_route);
             let result = self.is_loop(routes, &position, &direction);
             routes.remove(&new_route);
-            return result;
+            result
         }
     }
 }
@@ -146,7 +141,7 @@ pub fn part_one(input: &str) -> Option<u64> {
     let mut positions: HashSet<(isize, isize)> = std::collections::HashSet::new();
     let mut grid = Grid::new(input);
     let mut guard_position = grid.init_position;
-    let mut guard_direction = grid.init_direction.clone();
+    let mut guard_direction = grid.init_direction;
 
     while grid.is_valid_position(&guard_position) {
         positions.insert(guard_position);
@@ -159,7 +154,7 @@ pub fn part_one(input: &str) -> Option<u64> {
 pub fn part_two(input: &str) -> Option<u64> {
     let mut grid = Grid::new(input);
     let mut guard_position = grid.init_position;
-    let mut guard_direction = grid.init_direction.clone();
+    let mut guard_direction = grid.init_direction;
     let mut positions: HashSet<(isize, isize)> = HashSet::new();
     let mut visited: HashSet<((isize, isize), Direction)> = std::collections::HashSet::new();
 
@@ -28,7 +28,7 @@ impl Equation {
     }
 
     fn evaluate(&self, numbers: &[u64], acc: u64) -> bool {
-        if numbers.len() == 0 {
+        if numbers.is_empty() {
             return acc == self.target;
         }
         if acc > self.target {
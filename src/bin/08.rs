@@ -1,13 +1,11 @@
-use std::{
-    collections::{HashMap, HashSet},
-    hash::Hash,
-};
+use std::collections::{HashMap, HashSet};
 
 advent_of_code::solution!(8);
 
 struct Grid {
     width: usize,
     height: usize,
+    #[allow(dead_code)]
     cells: Vec<Vec<char>>,
     antenna: HashMap<char, Vec<(usize, usize)>>,
 }
@@ -62,26 +60,18 @@ impl Grid {
     ) {
         let mut prev = point_1;
         let mut next = point_2;
-        loop {
-            if let Some(antinode) = self.get_antinode(prev, next) {
-                antinodes.insert(antinode);
-                prev = next;
-                next = antinode;
-            } else {
-                break;
-            }
+        while let Some(antinode) = self.get_antinode(prev, next) {
+            antinodes.insert(antinode);
+            prev = next;
+            next = antinode;
         }
 
         prev = point_2;
         next = point_1;
-        loop {
-            if let Some(antinode) = self.get_antinode(prev, next) {
-                antinodes.insert(antinode);
-                prev = next;
-                next = antinode;
-            } else {
-                break;
-            }
+        while let Some(antinode) = self.get_antinode(prev, next) {
+            antinodes.insert(antinode);
+            prev = next;
+            next = antinode;
         }
     }
 }
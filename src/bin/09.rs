@@ -1,4 +1,30 @@
-advent_of_code::solution!(9);
+use advent_of_code::template::generate::{Generator, Rng};
+
+advent_of_code::solution!(9, generator = DiskMap, alternatives = [2 => part_two_spans]);
+
+/// Generates a disk map of `size` digits, alternating files (1-9 blocks) and free space (0-9 blocks).
+struct DiskMap;
+
+impl Generator for DiskMap {
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        let mut map: String = (0..size)
+            .map(|i| {
+                let blocks = if i % 2 == 0 {
+                    rng.range(1, 9)
+                } else {
+                    rng.range(0, 9)
+                };
+                char::from(b'0' + blocks as u8)
+            })
+            .collect();
+        map.push('\n');
+        map
+    }
+
+    fn default_size(&self) -> usize {
+        20000
+    }
+}
 
 struct Disk {
     files: Vec<String>,
@@ -95,7 +121,7 @@ impl Disk {
     fn get_checksum(&self) -> u64 {
         let mut sum = 0;
         for (i, c) in self.files.iter().enumerate() {
-            if let Some(num) = c.parse::<u64>().ok() {
+            if let Ok(num) = c.parse::<u64>() {
                 sum += num * i as u64;
             }
         }
@@ -117,6 +143,45 @@ pub fn part_two(input: &str) -> Option<u64> {
     Some(disk.get_checksum())
 }
 
+/// Same as [`part_two`], but moves whole spans instead of single blocks.
+pub fn part_two_spans(input: &str) -> Option<u64> {
+    // (position, length) of every file, indexed by id, and of every free span.
+    let mut files: Vec<(usize, usize)> = vec![];
+    let mut free: Vec<(usize, usize)> = vec![];
+    let mut position = 0;
+
+    for (i, c) in input.trim().chars().enumerate() {
+        let len = c.to_digit(10)? as usize;
+        if i % 2 == 0 {
+            files.push((position, len));
+        } else if len > 0 {
+            free.push((position, len));
+        }
+        position += len;
+    }
+
+    for file in files.iter_mut().rev() {
+        let (file_pos, file_len) = *file;
+        if let Some(span) = free
+            .iter_mut()
+            .take_while(|(pos, _)| *pos < file_pos)
+            .find(|(_, len)| *len >= file_len)
+        {
+            file.0 = span.0;
+            span.0 += file_len;
+            span.1 -= file_len;
+        }
+    }
+
+    Some(
+        files
+            .iter()
+            .enumerate()
+            .map(|(id, (pos, len))| (*pos..pos + len).map(|p| (id * p) as u64).sum::<u64>())
+            .sum(),
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -132,4 +197,10 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(2858));
     }
+
+    #[test]
+    fn test_part_two_spans() {
+        let result = part_two_spans(&advent_of_code::template::read_file("examples", DAY));
+        assert_eq!(result, Some(2858));
+    }
 }
@@ -14,7 +14,7 @@ impl Grid {
         for (i, line) in input.lines().enumerate() {
             let mut row: Vec<u8> = vec![];
             for (j, c) in line.chars().enumerate() {
-                row.push(c as u8 - '0' as u8);
+                row.push(c as u8 - b'0');
                 if c == '0' {
                     heads.push((j, i));
                 }
@@ -1,9 +1,12 @@
 use std::collections::HashMap;
 
+use advent_of_code::aoc_count;
+
 advent_of_code::solution!(11);
 
 fn traverse(number: u64, level: usize, memo: &mut HashMap<(u64, usize), usize>) -> usize {
     if memo.contains_key(&(number, level)) {
+        aoc_count!("memo_hits");
         return memo[&(number, level)];
     }
     if level == 75 {
@@ -12,7 +15,7 @@ fn traverse(number: u64, level: usize, memo: &mut HashMap<(u64, usize), usize>)
     let mut result = 0;
     if number == 0 {
         result += traverse(1, level + 1, memo);
-    } else if number.to_string().len() % 2 == 0 {
+    } else if number.to_string().len().is_multiple_of(2) {
         let y = number.to_string();
         let mid = y.len() / 2;
         result += traverse(y[..mid].parse().unwrap(), level + 1, memo);
@@ -45,13 +45,13 @@ impl Machine {
         let y = (self.prize.0 * self.button_b.1).abs_diff(self.prize.1 * self.button_b.0);
         let x = (self.button_a.0 * self.button_b.1).abs_diff(self.button_a.1 * self.button_b.0);
 
-        if y % x != 0 {
+        if !y.is_multiple_of(x) {
             return 0;
         }
 
         let count_a: u64 = y / x;
         let temp = self.prize.0 - count_a * self.button_a.0;
-        if temp % self.button_b.0 != 0 {
+        if !temp.is_multiple_of(self.button_b.0) {
             return 0;
         }
         let count_b = temp / self.button_b.0;
@@ -85,8 +85,8 @@ pub fn part_two(input: &str) -> Option<u64> {
 
     for machine in machines {
         let mut machine = Machine::new(machine);
-        machine.prize.0 = machine.prize.0 + 10000000000000;
-        machine.prize.1 = machine.prize.1 + 10000000000000;
+        machine.prize.0 += 10000000000000;
+        machine.prize.1 += 10000000000000;
 
         result += machine.calculate_cost();
     }
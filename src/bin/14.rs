@@ -1,7 +1,115 @@
-use std::{collections::HashMap, io::Write};
+use std::collections::HashMap;
 
-advent_of_code::solution!(14);
+use advent_of_code::template::generate::{Generator, Rng};
+use advent_of_code::template::parse::{InputError, ParseExt};
+use advent_of_code::template::{solution::Solution, Day};
 
+advent_of_code::solution!(solution = RestroomRedoubt, generator = Robots);
+
+pub struct RestroomRedoubt;
+
+/// Robots on a floor of `width` x `height` tiles.
+pub struct Floor {
+    robots: Vec<Robot>,
+    width: usize,
+    height: usize,
+}
+
+impl Floor {
+    fn new(input: &str, width: usize, height: usize) -> Result<Self, InputError> {
+        let robots = input
+            .lines()
+            .map(|line| Robot::new(line, width as isize, height as isize))
+            .collect::<Result<_, _>>()?;
+
+        Ok(Floor {
+            robots,
+            width,
+            height,
+        })
+    }
+}
+
+impl Solution for RestroomRedoubt {
+    const DAY: Day = advent_of_code::day!(14);
+    const TITLE: Option<&'static str> = Some("Restroom Redoubt");
+
+    type Input<'a> = Floor;
+    type Answer1 = Option<u64>;
+    type Answer2 = Option<u64>;
+
+    fn parse(&self, input: &str) -> Result<Floor, InputError> {
+        Floor::new(input, 101, 103)
+    }
+
+    /// The example uses a smaller floor.
+    fn parse_example(&self, input: &str) -> Result<Floor, InputError> {
+        Floor::new(input, 11, 7)
+    }
+
+    fn part_one(&self, floor: &Floor) -> Option<u64> {
+        let mut scores: HashMap<usize, u64> = HashMap::new();
+        scores.insert(1, 0);
+        scores.insert(2, 0);
+        scores.insert(3, 0);
+        scores.insert(4, 0);
+
+        for robot in &floor.robots {
+            let mut robot = robot.clone();
+            robot.move_on_map(100);
+            let quadrant = robot.get_quadrant();
+            *scores.entry(quadrant).or_insert(1) += 1;
+        }
+
+        Some(scores[&1] * scores[&2] * scores[&3] * scores[&4])
+    }
+
+    /// The first second at which the robots draw a line, the frame of the christmas tree.
+    /// The positions repeat after `width * height` seconds.
+    fn part_two(&self, floor: &Floor) -> Option<u64> {
+        let (width, height) = (floor.width, floor.height);
+        let mut robots = floor.robots.clone();
+
+        for seconds in 1..=width * height {
+            let mut map = vec![false; width * height];
+            for robot in &mut robots {
+                robot.move_on_map(1);
+                map[robot.y as usize * width + robot.x as usize] = true;
+            }
+
+            if check_straight_line(&map, width, height) {
+                return Some(seconds as u64);
+            }
+        }
+
+        None
+    }
+}
+
+/// Generates `size` robots on the full-size map.
+struct Robots;
+
+impl Generator for Robots {
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        (0..size)
+            .map(|_| {
+                format!(
+                    "p={},{} v={},{}\n",
+                    rng.range(0, 100),
+                    rng.range(0, 102),
+                    rng.range(-99, 99),
+                    rng.range(-99, 99)
+                )
+            })
+            .collect()
+    }
+
+    fn default_size(&self) -> usize {
+        500
+    }
+}
+
+#[derive(Clone)]
 struct Robot {
     max_x: isize,
     max_y: isize,
@@ -11,28 +119,19 @@ struct Robot {
 }
 
 impl Robot {
-    fn new(input: &str, max_x: isize, max_y: isize) -> Robot {
-        let position_re = regex::Regex::new(r"p=(-?\d+),(-?\d+)").unwrap();
-        let position = position_re.captures(input).unwrap();
-        let position: (isize, isize) = (
-            position.get(1).unwrap().as_str().parse().unwrap(),
-            position.get(2).unwrap().as_str().parse().unwrap(),
-        );
-
-        let velocity_re = regex::Regex::new(r"v=(-?\d+),(-?\d+)").unwrap();
-        let velocity = velocity_re.captures(input).unwrap();
-        let velocity: (isize, isize) = (
-            velocity.get(1).unwrap().as_str().parse().unwrap(),
-            velocity.get(2).unwrap().as_str().parse().unwrap(),
-        );
-
-        Robot {
-            max_x: max_x,
-            max_y: max_y,
-            velocity: velocity,
-            x: position.0,
-            y: position.1,
-        }
+    /// Parses a line like `p=0,4 v=3,-3`.
+    fn new(input: &str, max_x: isize, max_y: isize) -> Result<Robot, InputError> {
+        let (position, velocity) = input.try_split_once(" ")?;
+        let (x, y) = vector(position, "p=")?;
+        let velocity = vector(velocity, "v=")?;
+
+        Ok(Robot {
+            max_x,
+            max_y,
+            velocity,
+            x,
+            y,
+        })
     }
 
     fn move_on_map(&mut self, seconds: isize) {
@@ -52,11 +151,11 @@ impl Robot {
         let h_half = self.max_y / 2;
         if self.x < w_half && self.y < h_half {
             1
-        } else if self.x >= w_half + 1 && self.y < h_half {
+        } else if self.x > w_half && self.y < h_half {
             2
-        } else if self.x >= w_half + 1 && self.y >= h_half + 1 {
+        } else if self.x > w_half && self.y > h_half {
             3
-        } else if self.x < w_half && self.y >= h_half + 1 {
+        } else if self.x < w_half && self.y > h_half {
             4
         } else {
             0
@@ -64,119 +163,62 @@ impl Robot {
     }
 }
 
-fn check_straight_line(map: &HashMap<(usize, usize), usize>, width: usize, height: usize) -> bool {
-    let mut count = 0;
+/// Parses a vector like `v=3,-3`.
+fn vector(input: &str, prefix: &str) -> Result<(isize, isize), InputError> {
+    let values = input
+        .strip_prefix(prefix)
+        .ok_or_else(|| InputError::at(input, format!("expected `{prefix}`")))?;
+    let (x, y) = values.try_split_once(",")?;
+    Ok((x.try_parse()?, y.try_parse()?))
+}
+
+/// Whether 10 robots stand next to each other in a row or a column.
+fn check_straight_line(map: &[bool], width: usize, height: usize) -> bool {
     for y in 0..height {
+        let mut count = 0;
         for x in 0..width {
-            if map.contains_key(&(x, y)) {
-                count += 1;
-                if count >= 10 {
-                    return true;
-                }
-            } else {
-                count = 0;
+            count = if map[y * width + x] { count + 1 } else { 0 };
+            if count >= 10 {
+                return true;
             }
         }
     }
     for x in 0..width {
+        let mut count = 0;
         for y in 0..height {
-            if map.contains_key(&(x, y)) {
-                count += 1;
-                if count >= 10 {
-                    return true;
-                }
-            } else {
-                count = 0;
+            count = if map[y * width + x] { count + 1 } else { 0 };
+            if count >= 10 {
+                return true;
             }
         }
     }
     false
 }
 
-fn print_visual(
-    map: &HashMap<(usize, usize), usize>,
-    width: usize,
-    height: usize,
-    seconds: isize,
-    file: &mut std::fs::File,
-) {
-    let mut output = String::new();
-    for y in 0..height {
-        for x in 0..width {
-            if map.contains_key(&(x, y)) {
-                output.push('#');
-            } else {
-                output.push('.');
-            }
-        }
-        output.push('\n');
-    }
-    file.write_all(format!("\nSeconds: {}\n", seconds).as_bytes())
-        .unwrap();
-    file.write_all(output.as_bytes()).unwrap();
-}
-
-pub fn part_one(input: &str) -> Option<u64> {
-    // let width: usize = 101;
-    // let height: usize = 103;
-    let width: usize = 11;
-    let height: usize = 7;
-    let mut scores: HashMap<usize, u64> = HashMap::new();
-    scores.insert(1, 0);
-    scores.insert(2, 0);
-    scores.insert(3, 0);
-    scores.insert(4, 0);
-
-    for line in input.lines() {
-        let mut robot = Robot::new(line, width as isize, height as isize);
-        robot.move_on_map(100);
-        let quadrant = robot.get_quadrant();
-        *scores.entry(quadrant).or_insert(1) += 1;
-    }
-
-    Some(scores[&1] * scores[&2] * scores[&3] * scores[&4])
-}
-
-pub fn part_two(input: &str) -> Option<u64> {
-    let width: usize = 101;
-    let height: usize = 103;
-    let mut robots: Vec<Robot> = vec![];
-    for line in input.lines() {
-        robots.push(Robot::new(line, width as isize, height as isize));
-    }
-
-    let mut file = std::fs::File::create("output.txt").unwrap();
-
-    for i in 1..=10000 {
-        let mut map: HashMap<(usize, usize), usize> = HashMap::new();
-        for robot in &mut robots {
-            robot.move_on_map(1);
-            *map.entry((robot.x as usize, robot.y as usize)).or_insert(0) += 1;
-        }
-
-        let is_straight = check_straight_line(&map, width, height);
-        if is_straight {
-            print_visual(&map, width, height, i, &mut file);
-        }
-        println!("Seconds: {} {}", i, is_straight);
-    }
-
-    None
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(12));
+        let input = advent_of_code::template::read_file("examples", DAY);
+        let floor = RestroomRedoubt.parse_example(&input).unwrap();
+        assert_eq!(RestroomRedoubt.part_one(&floor), Some(12));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
+        let input = advent_of_code::template::read_file("examples", DAY);
+        let floor = RestroomRedoubt.parse(&input).unwrap();
+        assert_eq!(RestroomRedoubt.part_two(&floor), None);
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let error = RestroomRedoubt
+            .parse("p=0,4 v=3,-3\np=6,x v=-1,-3\n")
+            .err()
+            .unwrap();
+        assert_eq!(error.message(), "expected isize, got `x`");
     }
 }
@@ -51,11 +51,12 @@ impl Grid {
         Grid {
             width: cells[0].len(),
             height: cells.len(),
-            cells: cells,
-            robot: robot,
+            cells,
+            robot,
         }
     }
 
+    #[allow(dead_code)]
     fn print(&self) {
         for i in 0..self.height {
             for j in 0..self.width {
@@ -4,7 +4,68 @@ use std::{
     ops::Add,
 };
 
-advent_of_code::solution!(16);
+use advent_of_code::{
+    aoc_count, aoc_span,
+    template::generate::{Generator, Rng},
+};
+
+advent_of_code::solution!(16, generator = Maze);
+
+/// Generates a square maze with a side length of `size`, with S in the bottom-left and E in the top-right corner.
+/// Some walls are removed after carving, so that there are several paths to the end.
+struct Maze;
+
+impl Generator for Maze {
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        // cells sit on odd coordinates, so the side length has to be odd.
+        let side = (size.max(5) - 1) / 2 * 2 + 1;
+        let mut cells = vec![vec!['#'; side]; side];
+
+        let start = (1, side - 2);
+        cells[start.1][start.0] = '.';
+        let mut stack = vec![start];
+        while let Some(&(x, y)) = stack.last() {
+            let next: Vec<(usize, usize)> = [(0, -2), (2, 0), (0, 2), (-2, 0)]
+                .iter()
+                .filter_map(|(dx, dy)| {
+                    let nx = x.checked_add_signed(*dx)?;
+                    let ny = y.checked_add_signed(*dy)?;
+                    (nx < side - 1 && ny < side - 1 && cells[ny][nx] == '#').then_some((nx, ny))
+                })
+                .collect();
+
+            if next.is_empty() {
+                stack.pop();
+                continue;
+            }
+
+            let (nx, ny) = next[rng.below(next.len())];
+            cells[(y + ny) / 2][(x + nx) / 2] = '.';
+            cells[ny][nx] = '.';
+            stack.push((nx, ny));
+        }
+
+        for (y, row) in cells.iter_mut().enumerate().take(side - 1).skip(1) {
+            for (x, cell) in row.iter_mut().enumerate().take(side - 1).skip(1) {
+                if (x + y) % 2 == 1 && rng.chance(0.1) {
+                    *cell = '.';
+                }
+            }
+        }
+
+        cells[start.1][start.0] = 'S';
+        cells[1][side - 2] = 'E';
+
+        cells
+            .iter()
+            .map(|row| row.iter().collect::<String>() + "\n")
+            .collect()
+    }
+
+    fn default_size(&self) -> usize {
+        141
+    }
+}
 
 #[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
 enum Direction {
@@ -114,9 +175,10 @@ impl Grid {
             y,
             direction,
             cost,
-            history,
+            history: _,
         })) = prio.pop()
         {
+            aoc_count!("heap_pops");
             if x == self.end.0 && y == self.end.1 && cost < min_cost {
                 min_cost = cost;
                 continue;
@@ -175,6 +237,7 @@ impl Grid {
             history,
         })) = prio.pop()
         {
+            aoc_count!("backtrack_pops");
             let mut history = history.unwrap();
             history.push((x, y));
 
@@ -218,16 +281,19 @@ impl Grid {
 }
 
 pub fn part_one(input: &str) -> Option<u64> {
-    let grid = Grid::new(input);
+    let grid = aoc_span!("parse", Grid::new(input));
 
-    Some(grid.dijkstra())
+    Some(aoc_span!("dijkstra", grid.dijkstra()))
 }
 
 pub fn part_two(input: &str) -> Option<u64> {
-    let grid = Grid::new(input);
-    let min_cost = grid.dijkstra();
+    let grid = aoc_span!("parse", Grid::new(input));
+    let min_cost = aoc_span!("dijkstra", grid.dijkstra());
 
-    Some(grid.dijkstra_backtrack(min_cost))
+    Some(aoc_span!(
+        "dijkstra_backtrack",
+        grid.dijkstra_backtrack(min_cost)
+    ))
 }
 
 #[cfg(test)]
@@ -1,4 +1,4 @@
-advent_of_code::solution!(17);
+advent_of_code::solution!(17, answers = (Option<String>, Option<Num>));
 
 type Num = u64;
 
@@ -34,7 +34,7 @@ impl Computer {
             register_b: b.parse().unwrap(),
             register_c: c.parse().unwrap(),
             instruction_pointer: 0,
-            program: program,
+            program,
             outputs: Vec::new(),
         }
     }
@@ -108,7 +108,7 @@ impl Computer {
         self.instruction_pointer = 0;
         self.outputs.clear();
         self.run();
-        return self.outputs.clone();
+        self.outputs.clone()
     }
 
     fn print_output(&self) -> String {
@@ -136,7 +136,7 @@ fn dfs(computer: &mut Computer, position: isize, curr: Num) -> Option<Num> {
             }
         }
     }
-    return None;
+    None
 }
 
 pub fn part_one(input: &str) -> Option<String> {
@@ -150,13 +150,7 @@ pub fn part_two(input: &str) -> Option<Num> {
     let mut computer = Computer::new(input);
     let position = computer.program.len() - 1;
 
-    let result = dfs(&mut computer, position as isize, 0);
-
-    if let Some(result) = result {
-        Some(result)
-    } else {
-        None
-    }
+    dfs(&mut computer, position as isize, 0)
 }
 
 #[cfg(test)]
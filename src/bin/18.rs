@@ -1,6 +1,8 @@
 use std::collections::HashSet;
 
-advent_of_code::solution!(18);
+use advent_of_code::aoc_span;
+
+advent_of_code::solution!(18, answers = (Option<u64>, Option<String>));
 
 struct Grid {
     width: usize,
@@ -42,8 +44,8 @@ impl Grid {
     fn find_shortest_path(&self) -> u64 {
         let mut step = 0;
         let mut visited: HashSet<(usize, usize)> = HashSet::new();
-        let mut stacks: Vec<(usize, usize)> = vec![(0 as usize, 0 as usize)];
-        while stacks.len() > 0 {
+        let mut stacks: Vec<(usize, usize)> = vec![(0_usize, 0_usize)];
+        while !stacks.is_empty() {
             let mut temp: Vec<(usize, usize)> = vec![];
             while let Some((x, y)) = stacks.pop() {
                 if x == self.width - 1 && y == self.height - 1 {
@@ -81,23 +83,23 @@ impl Grid {
 }
 
 pub fn part_one(input: &str) -> Option<u64> {
-    let mut grid = Grid::new(71, 71, input);
-    grid.make_corrupt(1024);
-    let result = grid.find_shortest_path();
+    let mut grid = aoc_span!("parse", Grid::new(71, 71, input));
+    aoc_span!("make_corrupt", grid.make_corrupt(1024));
+    let result = aoc_span!("find_shortest_path", grid.find_shortest_path());
 
     Some(result)
 }
 
 pub fn part_two(input: &str) -> Option<String> {
-    let mut grid = Grid::new(71, 71, input);
+    let mut grid = aoc_span!("parse", Grid::new(71, 71, input));
     let mut left = 0;
     let mut right = grid.bytes.len() - 1;
 
     while left < right {
         let mid = (left + right) / 2;
         grid.reset();
-        grid.make_corrupt((mid + 1) as u64);
-        let res = grid.find_shortest_path();
+        aoc_span!("make_corrupt", grid.make_corrupt((mid + 1) as u64));
+        let res = aoc_span!("find_shortest_path", grid.find_shortest_path());
 
         if res == 0 {
             right = mid;
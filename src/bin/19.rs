@@ -2,6 +2,7 @@ advent_of_code::solution!(19);
 
 use std::collections::HashMap;
 
+use advent_of_code::aoc_count;
 use trie_rs::{Trie, TrieBuilder};
 
 fn check_possible(
@@ -10,17 +11,18 @@ fn check_possible(
     max_len: &usize,
     design: &str,
 ) -> bool {
-    if design.len() == 0 {
+    if design.is_empty() {
         return true;
     }
     if memo.contains_key(design) {
+        aoc_count!("memo_hits");
         return memo[design];
     }
     for i in 1..=*max_len {
         if design.len() < i {
             continue;
         }
-        if !towers.exact_match(&design[0..i].as_bytes()) {
+        if !towers.exact_match(&design.as_bytes()[0..i]) {
             continue;
         }
 
@@ -40,10 +42,11 @@ fn get_possible_count(
     max_len: &usize,
     design: &str,
 ) -> u64 {
-    if design.len() == 0 {
+    if design.is_empty() {
         return 1;
     }
     if memo.contains_key(design) {
+        aoc_count!("memo_hits");
         return memo[design];
     }
     let mut result: u64 = 0;
@@ -51,7 +54,7 @@ fn get_possible_count(
         if design.len() < i {
             continue;
         }
-        if !towers.exact_match(&design[0..i].as_bytes()) {
+        if !towers.exact_match(&design.as_bytes()[0..i]) {
             continue;
         }
 
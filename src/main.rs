@@ -1,4 +1,8 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::{
+    all, crosscheck, download, generate, login, read, report, scaffold, scale, solve, stars, time,
+    verify,
+};
+use advent_of_code::template::{config, session};
 use args::{parse, AppArguments};
 
 #[cfg(feature = "today")]
@@ -7,8 +11,16 @@ use advent_of_code::template::Day;
 use std::process;
 
 mod args {
-    use advent_of_code::template::Day;
-    use std::process;
+    use advent_of_code::template::commands::crosscheck::Crosscheck;
+    use advent_of_code::template::commands::generate::Generation;
+    use advent_of_code::template::commands::report::Format;
+    use advent_of_code::template::commands::scale::Scaling;
+    use advent_of_code::template::commands::solve::parse_part;
+    use advent_of_code::template::commands::time::{
+        BarStyle, Columns, Instrumentation, Isolation, MergeStrategy, SortOrder, TableOptions,
+    };
+    use advent_of_code::template::{all_days, session, Day, Days};
+    use std::{path::PathBuf, process};
 
     pub enum AppArguments {
         Download {
@@ -23,18 +35,51 @@ mod args {
             overwrite: bool,
         },
         Solve {
-            day: Day,
+            days: Days,
             release: bool,
             dhat: bool,
+            allocs: bool,
+            spans: bool,
+            counters: bool,
+            part: Option<u8>,
             submit: Option<u8>,
         },
         All {
+            days: Days,
             release: bool,
+            registry: bool,
         },
         Time {
             all: bool,
-            day: Option<Day>,
+            days: Option<Days>,
+            except: Option<Days>,
             store: bool,
+            merge: MergeStrategy,
+            instrumentation: Instrumentation,
+            part: Option<u8>,
+            table: TableOptions,
+        },
+        Generate {
+            day: Day,
+            generation: Generation,
+        },
+        Scale {
+            day: Day,
+            scaling: Scaling,
+        },
+        Crosscheck {
+            day: Day,
+            crosscheck: Crosscheck,
+        },
+        Verify {
+            day: Option<Day>,
+            corpus: bool,
+        },
+        Stars,
+        Login,
+        Report {
+            format: Option<Format>,
+            output: Option<PathBuf>,
         },
         #[cfg(feature = "today")]
         Today,
@@ -44,17 +89,56 @@ mod args {
         let mut args = pico_args::Arguments::from_env();
 
         let app_args = match args.subcommand()?.as_deref() {
-            Some("all") => AppArguments::All {
-                release: args.contains("--release"),
-            },
+            Some("all") => {
+                let release = args.contains("--release");
+                let registry = args.contains("--registry");
+                let except: Option<Days> = args.opt_value_from_str("--except")?;
+                let days: Option<Days> = args.opt_free_from_str()?;
+                AppArguments::All {
+                    days: days
+                        .unwrap_or_else(|| all_days().collect())
+                        .without(except.as_ref()),
+                    release,
+                    registry,
+                }
+            }
             Some("time") => {
                 let all = args.contains("--all");
                 let store = args.contains("--store");
+                let merge = args.opt_value_from_str("--merge")?.unwrap_or_default();
+                let isolate = args.contains("--isolate");
+                let cpu = args.opt_value_from_str("--cpu")?;
+                let instrumentation = Instrumentation {
+                    isolation: (isolate || cpu.is_some()).then_some(Isolation { cpu }),
+                    allocations: args.contains("--allocs"),
+                    counters: args.contains("--counters"),
+                };
+
+                let defaults = TableOptions::default();
+                let table = TableOptions {
+                    columns: args
+                        .opt_value_from_str::<_, Columns>("--columns")?
+                        .map_or(defaults.columns, |c| c.0),
+                    sort: args
+                        .opt_value_from_str::<_, SortOrder>("--sort")?
+                        .unwrap_or(defaults.sort),
+                    bar: args.opt_value_from_str::<_, BarStyle>("--bars")?,
+                    totals: args.contains("--totals"),
+                };
+
+                let except = args.opt_value_from_str("--except")?;
+                let part = args.opt_value_from_fn("--part", parse_part)?;
 
                 AppArguments::Time {
                     all,
-                    day: args.opt_free_from_str()?,
+                    except,
+                    // free arguments are read last, or the value of an option would be taken for them.
+                    days: args.opt_free_from_str()?,
                     store,
+                    merge,
+                    instrumentation,
+                    part,
+                    table,
                 }
             }
             Some("download") => AppArguments::Download {
@@ -68,11 +152,77 @@ mod args {
                 download: args.contains("--download"),
                 overwrite: args.contains("--overwrite"),
             },
-            Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
-                release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
-                dhat: args.contains("--dhat"),
+            Some("solve") => {
+                let release = args.contains("--release");
+                let submit = args.opt_value_from_str("--submit")?;
+                let dhat = args.contains("--dhat");
+                let allocs = args.contains("--allocs");
+                let spans = args.contains("--spans");
+                let counters = args.contains("--counters");
+                let part = args.opt_value_from_fn("--part", parse_part)?;
+                let except: Option<Days> = args.opt_value_from_str("--except")?;
+                let days: Days = args.free_from_str()?;
+
+                AppArguments::Solve {
+                    days: days.without(except.as_ref()),
+                    release,
+                    submit,
+                    dhat,
+                    allocs,
+                    spans,
+                    counters,
+                    part,
+                }
+            }
+            Some("generate") => {
+                let defaults = Generation::default();
+                let generation = Generation {
+                    seed: args.opt_value_from_str("--seed")?.unwrap_or(defaults.seed),
+                    count: args
+                        .opt_value_from_str("--count")?
+                        .unwrap_or(defaults.count),
+                    size: args.opt_value_from_str("--size")?,
+                };
+                AppArguments::Generate {
+                    day: args.free_from_str()?,
+                    generation,
+                }
+            }
+            Some("scale") => {
+                let defaults = Scaling::default();
+                let scaling = Scaling {
+                    strategy: args
+                        .opt_value_from_str("--by")?
+                        .unwrap_or(defaults.strategy),
+                    steps: args
+                        .opt_value_from_str("--steps")?
+                        .unwrap_or(defaults.steps),
+                };
+                AppArguments::Scale {
+                    day: args.free_from_str()?,
+                    scaling,
+                }
+            }
+            Some("crosscheck") => {
+                let crosscheck = Crosscheck {
+                    seeds: args
+                        .opt_value_from_str("--seeds")?
+                        .unwrap_or(Crosscheck::default().seeds),
+                };
+                AppArguments::Crosscheck {
+                    day: args.free_from_str()?,
+                    crosscheck,
+                }
+            }
+            Some("verify") => AppArguments::Verify {
+                corpus: args.contains("--corpus"),
+                day: args.opt_free_from_str()?,
+            },
+            Some("stars") => AppArguments::Stars,
+            Some("login") => AppArguments::Login,
+            Some("report") => AppArguments::Report {
+                format: args.opt_value_from_str("--format")?,
+                output: args.opt_value_from_str("--output")?,
             },
             #[cfg(feature = "today")]
             Some("today") => AppArguments::Today,
@@ -88,7 +238,9 @@ mod args {
 
         let remaining = args.finish();
         if !remaining.is_empty() {
-            eprintln!("Warning: unknown argument(s): {remaining:?}.");
+            // a token passed as an argument must not end up in the terminal.
+            let warning = format!("Warning: unknown argument(s): {remaining:?}.");
+            eprintln!("{}", session::redact(&warning));
         }
 
         Ok(app_args)
@@ -96,14 +248,40 @@ mod args {
 }
 
 fn main() {
+    // validate `aoc.toml` before running any command.
+    config::get();
+    session::warn_if_committed();
+
     match parse() {
         Err(err) => {
-            eprintln!("Error: {err}");
+            eprintln!("Error: {}", session::redact(&err.to_string()));
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
+            AppArguments::All {
+                days,
+                release,
+                registry,
+            } => all::handle(&days, release, registry),
+            AppArguments::Time {
+                days,
+                except,
+                all,
+                store,
+                merge,
+                instrumentation,
+                part,
+                table,
+            } => time::handle(
+                days,
+                except.as_ref(),
+                all,
+                store,
+                merge,
+                instrumentation,
+                part,
+                &table,
+            ),
             AppArguments::Download { day } => download::handle(day),
             AppArguments::Read { day } => read::handle(day),
             AppArguments::Scaffold {
@@ -117,11 +295,22 @@ fn main() {
                 }
             }
             AppArguments::Solve {
-                day,
+                days,
                 release,
                 dhat,
+                allocs,
+                spans,
+                counters,
+                part,
                 submit,
-            } => solve::handle(day, release, dhat, submit),
+            } => solve::handle(&days, release, dhat, allocs, spans, counters, part, submit),
+            AppArguments::Generate { day, generation } => generate::handle(day, generation),
+            AppArguments::Scale { day, scaling } => scale::handle(day, scaling),
+            AppArguments::Crosscheck { day, crosscheck } => crosscheck::handle(day, crosscheck),
+            AppArguments::Verify { day, corpus } => verify::handle(day, corpus),
+            AppArguments::Stars => stars::handle(),
+            AppArguments::Login => login::handle(),
+            AppArguments::Report { format, output } => report::handle(format, output),
             #[cfg(feature = "today")]
             AppArguments::Today => {
                 match Day::today() {
@@ -0,0 +1,24 @@
+//! Runs every registered day in one binary, see `cargo all --registry`.
+//! `cargo scaffold` adds new days to this list.
+
+advent_of_code::registry! {
+    day01 => "bin/01.rs",
+    day02 => "bin/02.rs",
+    day03 => "bin/03.rs",
+    day04 => "bin/04.rs",
+    day05 => "bin/05.rs",
+    day06 => "bin/06.rs",
+    day07 => "bin/07.rs",
+    day08 => "bin/08.rs",
+    day09 => "bin/09.rs",
+    day10 => "bin/10.rs",
+    day11 => "bin/11.rs",
+    day12 => "bin/12.rs",
+    day13 => "bin/13.rs",
+    day14 => "bin/14.rs",
+    day15 => "bin/15.rs",
+    day16 => "bin/16.rs",
+    day17 => "bin/17.rs",
+    day18 => "bin/18.rs",
+    day19 => "bin/19.rs",
+}
@@ -0,0 +1,147 @@
+/// Module that counts heap allocations of solution parts.
+/// The allocator is installed by the `solution!` macro if the `allocs` feature is enabled, see `cargo solve --allocs`.
+/// It only counts while a part is measured.
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    fmt::Display,
+    sync::atomic::{AtomicBool, AtomicI64, AtomicU64, Ordering},
+};
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static COUNT: AtomicU64 = AtomicU64::new(0);
+static BYTES: AtomicU64 = AtomicU64::new(0);
+/// Bytes allocated minus bytes freed since the measurement started.
+/// Can become negative if memory allocated before the measurement is freed.
+static CURRENT: AtomicI64 = AtomicI64::new(0);
+static PEAK: AtomicI64 = AtomicI64::new(0);
+
+/// Global allocator that forwards to the system allocator and counts allocations while enabled.
+pub struct CountingAlloc;
+
+#[allow(clippy::cast_possible_wrap)]
+fn record_alloc(size: usize) {
+    if ENABLED.load(Ordering::Relaxed) {
+        COUNT.fetch_add(1, Ordering::Relaxed);
+        BYTES.fetch_add(size as u64, Ordering::Relaxed);
+        let current = CURRENT.fetch_add(size as i64, Ordering::Relaxed) + size as i64;
+        PEAK.fetch_max(current, Ordering::Relaxed);
+    }
+}
+
+#[allow(clippy::cast_possible_wrap)]
+fn record_dealloc(size: usize) {
+    if ENABLED.load(Ordering::Relaxed) {
+        CURRENT.fetch_sub(size as i64, Ordering::Relaxed);
+    }
+}
+
+// SAFETY: all calls are forwarded to `System` unchanged, counting has no effect on the memory handed out.
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        record_dealloc(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            record_dealloc(layout.size());
+            record_alloc(new_size);
+        }
+        new_ptr
+    }
+}
+
+/// Heap usage of a solution part.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Allocations {
+    /// Number of allocations, including reallocations.
+    pub count: u64,
+    /// Total bytes allocated.
+    pub bytes: u64,
+    /// Peak of bytes that were allocated by the part and not yet freed.
+    pub peak: u64,
+}
+
+impl Display for Allocations {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} allocs / {} B, peak {} B",
+            self.count, self.bytes, self.peak
+        )
+    }
+}
+
+impl Allocations {
+    /// Parses the output of the `Display` implementation.
+    pub fn parse(s: &str) -> Option<Self> {
+        let (count, rest) = s.trim().split_once(" allocs / ")?;
+        let (bytes, peak) = rest.split_once(" B, peak ")?;
+
+        Some(Allocations {
+            count: count.parse().ok()?,
+            bytes: bytes.parse().ok()?,
+            peak: peak.strip_suffix(" B")?.parse().ok()?,
+        })
+    }
+}
+
+/// Runs `f` and counts its heap allocations.
+/// Counts are only meaningful if [`CountingAlloc`] is the global allocator.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Allocations) {
+    COUNT.store(0, Ordering::Relaxed);
+    BYTES.store(0, Ordering::Relaxed);
+    CURRENT.store(0, Ordering::Relaxed);
+    PEAK.store(0, Ordering::Relaxed);
+
+    ENABLED.store(true, Ordering::SeqCst);
+    let result = f();
+    ENABLED.store(false, Ordering::SeqCst);
+
+    let allocations = Allocations {
+        count: COUNT.load(Ordering::Relaxed),
+        bytes: BYTES.load(Ordering::Relaxed),
+        peak: PEAK.load(Ordering::Relaxed).try_into().unwrap_or(0),
+    };
+
+    (result, allocations)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Allocations;
+
+    #[test]
+    fn round_trips_display() {
+        let allocations = Allocations {
+            count: 12,
+            bytes: 3456,
+            peak: 2048,
+        };
+        assert_eq!(allocations.to_string(), "12 allocs / 3456 B, peak 2048 B");
+        assert_eq!(
+            Allocations::parse(&allocations.to_string()),
+            Some(allocations)
+        );
+        assert_eq!(Allocations::parse("12 allocs"), None);
+    }
+}
@@ -0,0 +1,361 @@
+/// Module that keeps a ledger of accepted answers, i.e. the stars earned so far.
+use std::{collections::HashMap, fs, io::Error, str::FromStr};
+use tinyjson::JsonValue;
+
+use crate::template::{config, Day};
+
+static ANSWERS_FILE_NAME: &str = "answers.json";
+
+/// An answer that was accepted by the advent of code website.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Answer {
+    pub value: String,
+    /// Unix timestamp (seconds) of the accepted submission.
+    pub completed_at: u64,
+}
+
+/// Represents the accepted answers for a single day.
+#[derive(Clone, Debug)]
+pub struct DayAnswers {
+    pub day: Day,
+    pub title: Option<String>,
+    pub part_1: Option<Answer>,
+    pub part_2: Option<Answer>,
+}
+
+impl DayAnswers {
+    /// Number of stars earned for this day.
+    pub fn stars(&self) -> u8 {
+        u8::from(self.part_1.is_some()) + u8::from(self.part_2.is_some())
+    }
+}
+
+/// Represents the accepted answers for a set of days.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default)]
+pub struct Answers {
+    pub data: Vec<DayAnswers>,
+}
+
+impl Answers {
+    /// Dehydrate answers to a JSON file.
+    pub fn store_file(&self) -> Result<(), Error> {
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(config::get().paths.data.join(ANSWERS_FILE_NAME))?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate answers from a JSON file. If not present, returns an empty ledger.
+    pub fn read_from_file() -> Self {
+        fs::read_to_string(config::get().paths.data.join(ANSWERS_FILE_NAME))
+            .map_err(|x| x.to_string())
+            .and_then(Answers::try_from)
+            .unwrap_or_default()
+    }
+
+    /// Record an accepted answer for one part of a day.
+    /// Answers that were already accepted keep their original completion time.
+    pub fn record(
+        &mut self,
+        day: Day,
+        part: u8,
+        answer: Answer,
+        title: Option<String>,
+    ) -> Result<(), String> {
+        if !matches!(part, 1 | 2) {
+            return Err(format!("Invalid part {part}, expected 1 or 2."));
+        }
+
+        let index = match self.data.iter().position(|a| a.day == day) {
+            Some(index) => index,
+            None => {
+                self.data.push(DayAnswers {
+                    day,
+                    title: None,
+                    part_1: None,
+                    part_2: None,
+                });
+                self.data.sort_unstable_by_key(|a| a.day);
+                self.data.iter().position(|a| a.day == day).unwrap()
+            }
+        };
+
+        let entry = &mut self.data[index];
+
+        if title.is_some() {
+            entry.title = title;
+        }
+
+        let slot = match part {
+            1 => &mut entry.part_1,
+            _ => &mut entry.part_2,
+        };
+
+        if slot.is_none() {
+            *slot = Some(answer);
+        }
+
+        Ok(())
+    }
+
+    pub fn get(&self, day: Day) -> Option<&DayAnswers> {
+        self.data.iter().find(|a| a.day == day)
+    }
+
+    /// Sum up the stars earned over all days.
+    pub fn total_stars(&self) -> u32 {
+        self.data.iter().map(|a| u32::from(a.stars())).sum()
+    }
+}
+
+/// Reads the puzzle title from the description downloaded by aoc-cli, if present.
+/// The description starts with a heading like `\--- Day 1: Historian Hysteria ---`.
+pub fn read_puzzle_title(day: Day) -> Option<String> {
+    let description =
+        fs::read_to_string(config::get().paths.puzzles.join(format!("{day}.md"))).ok()?;
+    parse_puzzle_title(&description)
+}
+
+fn parse_puzzle_title(description: &str) -> Option<String> {
+    let heading = description.lines().find(|l| l.contains("--- Day "))?;
+    let (_, title) = heading.split_once(": ")?;
+    let title = title.trim_end().trim_end_matches("---").trim();
+
+    if title.is_empty() {
+        None
+    } else {
+        Some(title.to_string())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<Answers> for JsonValue {
+    fn from(value: Answers) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Answers {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(Answers {
+            data: json_data
+                .iter()
+                .map(DayAnswers::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Answer> for JsonValue {
+    fn from(value: &Answer) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("value".into(), JsonValue::String(value.value.clone()));
+        #[allow(clippy::cast_precision_loss)]
+        map.insert(
+            "completed_at".into(),
+            JsonValue::Number(value.completed_at as f64),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Answer {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected answer to be a JSON object.")?;
+
+        let answer = json
+            .get("value")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected answer.value to be a string.")?;
+
+        let completed_at = json
+            .get("completed_at")
+            .and_then(|v| v.get::<f64>().copied())
+            .ok_or("Expected answer.completed_at to be a number.")?;
+
+        Ok(Answer {
+            value: answer.clone(),
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            completed_at: completed_at as u64,
+        })
+    }
+}
+
+impl From<&DayAnswers> for JsonValue {
+    fn from(value: &DayAnswers) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+
+        map.insert(
+            "title".into(),
+            match &value.title {
+                Some(x) => JsonValue::String(x.clone()),
+                None => JsonValue::Null,
+            },
+        );
+
+        map.insert(
+            "part_1".into(),
+            match &value.part_1 {
+                Some(x) => JsonValue::from(x),
+                None => JsonValue::Null,
+            },
+        );
+
+        map.insert(
+            "part_2".into(),
+            match &value.part_2 {
+                Some(x) => JsonValue::from(x),
+                None => JsonValue::Null,
+            },
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for DayAnswers {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected answers to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected answers.day to be a Day struct.")?;
+
+        let title = json
+            .get("title")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected answers.title to be null or string.")?;
+
+        let parse_part = |key: &str| -> Result<Option<Answer>, String> {
+            match json.get(key) {
+                Some(v) if v.is_null() => Ok(None),
+                Some(v) => Answer::try_from(v).map(Some),
+                None => Err(format!("Expected answers.{key} to be null or object.")),
+            }
+        };
+
+        Ok(DayAnswers {
+            day,
+            title: title.cloned(),
+            part_1: parse_part("part_1")?,
+            part_2: parse_part("part_2")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_puzzle_title, Answer, Answers};
+    use crate::day;
+
+    fn answer(value: &str, completed_at: u64) -> Answer {
+        Answer {
+            value: value.into(),
+            completed_at,
+        }
+    }
+
+    #[test]
+    fn records_answers() {
+        let mut answers = Answers::default();
+        answers
+            .record(day!(2), 1, answer("42", 10), Some("Foo".into()))
+            .unwrap();
+        answers.record(day!(1), 2, answer("7", 20), None).unwrap();
+        answers.record(day!(2), 2, answer("43", 30), None).unwrap();
+
+        assert_eq!(answers.data.len(), 2);
+        assert_eq!(answers.data[0].day, day!(1));
+        assert_eq!(answers.get(day!(2)).unwrap().title, Some("Foo".into()));
+        assert_eq!(answers.get(day!(2)).unwrap().stars(), 2);
+        assert_eq!(answers.total_stars(), 3);
+    }
+
+    #[test]
+    fn rejects_invalid_parts() {
+        let mut answers = Answers::default();
+        assert_eq!(
+            answers.record(day!(1), 3, answer("42", 10), None),
+            Err("Invalid part 3, expected 1 or 2.".into())
+        );
+        assert!(answers.get(day!(1)).is_none());
+    }
+
+    #[test]
+    fn keeps_first_completion() {
+        let mut answers = Answers::default();
+        answers.record(day!(1), 1, answer("42", 10), None).unwrap();
+        answers.record(day!(1), 1, answer("42", 99), None).unwrap();
+        assert_eq!(answers.get(day!(1)).unwrap().part_1, Some(answer("42", 10)));
+    }
+
+    #[test]
+    fn round_trips_json() {
+        let mut answers = Answers::default();
+        answers
+            .record(
+                day!(3),
+                1,
+                answer("161", 1_733_212_800),
+                Some("Mull It Over".into()),
+            )
+            .unwrap();
+
+        let json = tinyjson::JsonValue::from(answers).stringify().unwrap();
+        let parsed = Answers::try_from(json).unwrap();
+        let day = parsed.get(day!(3)).unwrap();
+
+        assert_eq!(day.title, Some("Mull It Over".into()));
+        assert_eq!(day.part_1, Some(answer("161", 1_733_212_800)));
+        assert_eq!(day.part_2, None);
+    }
+
+    #[test]
+    fn parses_puzzle_titles() {
+        let description =
+            "\\--- Day 1: Historian Hysteria ---\n----------\n\nThe *Chief Historian*...";
+        assert_eq!(
+            parse_puzzle_title(description),
+            Some("Historian Hysteria".into())
+        );
+        assert_eq!(parse_puzzle_title("no heading"), None);
+    }
+}
@@ -1,16 +1,25 @@
 /// Wrapper module around the "aoc-cli" command-line.
 use std::{
     fmt::Display,
+    io::{BufRead, BufReader, Read},
     process::{Command, Output, Stdio},
+    thread,
+    time::{Duration, Instant},
 };
 
-use crate::template::Day;
+use crate::template::session::{self, Session};
+use crate::template::{config, Day};
+
+/// Environment variable that aoc-cli reads the session from.
+const SESSION_ENV_VAR: &str = "ADVENT_OF_CODE_SESSION";
 
 #[derive(Debug)]
 pub enum AocCommandError {
     CommandNotFound,
     CommandNotCallable,
     BadExitStatus(Output),
+    /// The call took longer than `timeouts.aoc_cli_secs` in `aoc.toml`.
+    TimedOut(Duration),
 }
 
 impl Display for AocCommandError {
@@ -21,6 +30,9 @@ impl Display for AocCommandError {
             AocCommandError::BadExitStatus(_) => {
                 write!(f, "aoc-cli exited with a non-zero status.")
             }
+            AocCommandError::TimedOut(timeout) => {
+                write!(f, "aoc-cli did not finish within {timeout:?}.")
+            }
         }
     }
 }
@@ -77,22 +89,29 @@ pub fn submit(day: Day, part: u8, result: &str) -> Result<Output, AocCommandErro
     let mut args = build_args("submit", &[], day);
     args.push(part.to_string());
     args.push(result.to_string());
+
+    // stdout is captured, so that the verdict can be inspected.
     call_aoc_cli(&args)
 }
 
+/// Checks whether the output of a `submit` call reports the answer as correct.
+pub fn is_correct_answer(output: &Output) -> bool {
+    String::from_utf8_lossy(&output.stdout).contains("That's the right answer")
+}
+
 fn get_input_path(day: Day) -> String {
-    format!("data/inputs/{day}.txt")
+    let path = config::get().paths.inputs.join(format!("{day}.txt"));
+    path.to_string_lossy().to_string()
 }
 
 fn get_puzzle_path(day: Day) -> String {
-    format!("data/puzzles/{day}.md")
+    let path = config::get().paths.puzzles.join(format!("{day}.md"));
+    path.to_string_lossy().to_string()
 }
 
-fn get_year() -> Option<u16> {
-    match std::env::var("AOC_YEAR") {
-        Ok(x) => x.parse().ok().or(None),
-        Err(_) => None,
-    }
+/// The year of the puzzles, see `year` in `aoc.toml` or `AOC_YEAR`.
+pub fn get_year() -> Option<u16> {
+    config::get().year
 }
 
 fn build_args(command: &str, args: &[String], day: Day) -> Vec<String> {
@@ -110,12 +129,7 @@ fn build_args(command: &str, args: &[String], day: Day) -> Vec<String> {
 
 fn call_aoc_cli(args: &[String]) -> Result<Output, AocCommandError> {
     // println!("Calling >aoc with: {}", args.join(" "));
-    let output = Command::new("aoc")
-        .args(args)
-        .stdout(Stdio::inherit())
-        .stderr(Stdio::inherit())
-        .output()
-        .map_err(|_| AocCommandError::CommandNotCallable)?;
+    let output = run(args)?;
 
     if output.status.success() {
         Ok(output)
@@ -123,3 +137,86 @@ fn call_aoc_cli(args: &[String]) -> Result<Output, AocCommandError> {
         Err(AocCommandError::BadExitStatus(output))
     }
 }
+
+/// Runs aoc-cli with the session, killing it if it exceeds the configured timeout.
+/// Its output is forwarded with the session token redacted, stdout is also captured.
+fn run(args: &[String]) -> Result<Output, AocCommandError> {
+    let session = Session::read();
+
+    let mut command = Command::new("aoc");
+    command
+        .args(args)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    if let Some(session) = &session {
+        command.env(SESSION_ENV_VAR, session.token());
+    }
+
+    let mut child = command
+        .spawn()
+        .map_err(|_| AocCommandError::CommandNotCallable)?;
+
+    // forward output on other threads, so that a full pipe can not block the child.
+    let token = session.map(|session| session.token().to_string());
+    let stdout = child
+        .stdout
+        .take()
+        .map(|stdout| forward(stdout, token.clone(), false));
+    let stderr = child
+        .stderr
+        .take()
+        .map(|stderr| forward(stderr, token, true));
+
+    let timeout = config::get().aoc_cli_timeout;
+    let start = Instant::now();
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break status,
+            Ok(None) if timeout.is_some_and(|timeout| start.elapsed() >= timeout) => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(AocCommandError::TimedOut(timeout.unwrap()));
+            }
+            Ok(None) => thread::sleep(Duration::from_millis(20)),
+            Err(_) => return Err(AocCommandError::CommandNotCallable),
+        }
+    };
+
+    let join = |reader: Option<thread::JoinHandle<Vec<u8>>>| {
+        reader
+            .and_then(|reader| reader.join().ok())
+            .unwrap_or_default()
+    };
+
+    Ok(Output {
+        status,
+        stdout: join(stdout),
+        stderr: join(stderr),
+    })
+}
+
+/// Prints the lines of `reader` with the token redacted, and returns them.
+fn forward(
+    reader: impl Read + Send + 'static,
+    token: Option<String>,
+    to_stderr: bool,
+) -> thread::JoinHandle<Vec<u8>> {
+    thread::spawn(move || {
+        let mut reader = BufReader::new(reader);
+        let mut captured = vec![];
+        let mut line = vec![];
+
+        while matches!(reader.read_until(b'\n', &mut line), Ok(n) if n > 0) {
+            let text = session::redact_token(&String::from_utf8_lossy(&line), token.as_deref());
+            if to_stderr {
+                eprint!("{text}");
+            } else {
+                print!("{text}");
+            }
+            captured.extend_from_slice(text.as_bytes());
+            line.clear();
+        }
+
+        captured
+    })
+}
@@ -1,5 +1,34 @@
-use crate::template::{all_days, run_multi::run_multi};
+use std::process::{Command, Stdio};
 
-pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+use crate::template::{
+    run_multi::{run_multi, Instrumentation},
+    Days,
+};
+
+pub fn handle(days: &Days, is_release: bool, is_registry: bool) {
+    if is_registry {
+        run_registry(days, is_release);
+        return;
+    }
+
+    run_multi(&days.0, is_release, false, Instrumentation::default(), None);
+}
+
+/// Runs every day in the single registry binary instead of one binary per day.
+fn run_registry(days: &Days, is_release: bool) {
+    let mut cmd_args = vec!["run", "--quiet"];
+    if is_release {
+        cmd_args.push("--release");
+    }
+    cmd_args.extend(["--features", "registry", "--bin", "registry", "--"]);
+
+    let mut cmd = Command::new("cargo")
+        .args(&cmd_args)
+        .args(days.iter().map(|day| day.to_string()))
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .spawn()
+        .unwrap();
+
+    cmd.wait().unwrap();
 }
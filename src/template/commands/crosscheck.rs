@@ -0,0 +1,8 @@
+use crate::template::run_multi::run_bin;
+use crate::template::Day;
+
+pub use crate::template::crosscheck::Crosscheck;
+
+pub fn handle(day: Day, crosscheck: Crosscheck) {
+    run_bin(day, crosscheck.to_args()).status().unwrap();
+}
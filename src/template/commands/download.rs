@@ -1,3 +1,4 @@
+use crate::template::session::{self, Session};
 use crate::template::{aoc_cli, Day};
 use std::process;
 
@@ -9,6 +10,12 @@ pub fn handle(day: Day) {
 
     if let Err(e) = aoc_cli::download(day) {
         eprintln!("failed to call aoc-cli: {e}");
+        if Session::read().is_none() {
+            eprintln!(
+                "No session found, store it with `cargo aoc-login` or set `{}`.",
+                session::ENV_VAR
+            );
+        }
         process::exit(1);
     };
 }
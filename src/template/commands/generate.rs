@@ -0,0 +1,8 @@
+use crate::template::run_multi::run_bin;
+use crate::template::Day;
+
+pub use crate::template::generate::Generation;
+
+pub fn handle(day: Day, generation: Generation) {
+    run_bin(day, generation.to_args()).status().unwrap();
+}
@@ -0,0 +1,42 @@
+use std::{
+    io::{self, IsTerminal},
+    process,
+};
+
+use crate::template::session::{self, Session, Source};
+
+/// Reads the session cookie from stdin and stores it in the user-level credential file.
+pub fn handle() {
+    if io::stdin().is_terminal() {
+        println!("Paste the `session` cookie of adventofcode.com and press enter:");
+    }
+
+    let mut input = String::new();
+    if let Err(e) = io::stdin().read_line(&mut input) {
+        eprintln!("Failed to read the session cookie: {e}");
+        process::exit(1);
+    }
+
+    let token = match session::parse_token(&input) {
+        Ok(token) => token,
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    };
+
+    match session::store(&token) {
+        Ok(path) => println!("🎄 Stored session in \"{}\".", path.display()),
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    }
+
+    if let Some(Source::Env) = Session::read().as_ref().map(Session::source) {
+        println!(
+            "Note: `{}` is set and takes precedence over the stored session.",
+            session::ENV_VAR
+        );
+    }
+}
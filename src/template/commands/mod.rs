@@ -1,6 +1,13 @@
 pub mod all;
+pub mod crosscheck;
 pub mod download;
+pub mod generate;
+pub mod login;
 pub mod read;
+pub mod report;
 pub mod scaffold;
+pub mod scale;
 pub mod solve;
+pub mod stars;
 pub mod time;
+pub mod verify;
@@ -0,0 +1,59 @@
+use std::{fs, path::PathBuf, process};
+
+use crate::template::answers::Answers;
+use crate::template::config;
+use crate::template::report;
+use crate::template::timings::Timings;
+
+pub use crate::template::report::Format;
+
+/// Writes a report in `format`, or the `output.format` of `aoc.toml`.
+pub fn handle(format: Option<Format>, output: Option<PathBuf>) {
+    let config = config::get();
+    let format = format.unwrap_or(config.format);
+
+    let timings = match Timings::read_from_file() {
+        Ok(timings) => timings,
+        Err(e) => {
+            eprintln!("Failed to read stored benchmarks: {e}");
+            process::exit(1);
+        }
+    };
+    let history = match Timings::read_history() {
+        Ok(history) => history,
+        Err(e) => {
+            eprintln!("Failed to read benchmark history: {e}");
+            process::exit(1);
+        }
+    };
+    let answers = Answers::read_from_file();
+
+    if timings.data.is_empty() && answers.data.is_empty() {
+        eprintln!("No stored timings or answers found. Run `cargo time --store` first.");
+        process::exit(1);
+    }
+
+    let path = output.unwrap_or_else(|| {
+        config
+            .paths
+            .folder("reports")
+            .join(format!("report.{}", format.extension()))
+    });
+
+    if let Some(parent) = path.parent() {
+        if let Err(e) = fs::create_dir_all(parent) {
+            eprintln!("Failed to create report directory: {e}");
+            process::exit(1);
+        }
+    }
+
+    match fs::write(&path, report::render(format, &timings, &history, &answers)) {
+        Ok(()) => {
+            println!("🎄 Wrote report to \"{}\".", path.display());
+        }
+        Err(e) => {
+            eprintln!("Failed to write report: {e}");
+            process::exit(1);
+        }
+    }
+}
@@ -1,10 +1,13 @@
 use std::{
-    fs::{File, OpenOptions},
+    fs::{self, File, OpenOptions},
     io::Write,
     process,
 };
 
-use crate::template::Day;
+use crate::template::{config, Day};
+
+/// Files that include every solution as a module: the registry binary and the library.
+const MODULE_LISTS: [&str; 2] = ["src/registry.rs", "src/y2024.rs"];
 
 const MODULE_TEMPLATE: &str =
     include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
@@ -27,10 +30,47 @@ fn create_file(path: &str) -> Result<File, std::io::Error> {
         .open(path)
 }
 
+/// Adds the day to a list of modules, if it is not listed yet.
+fn register_module(path: &str, day: Day) -> Result<bool, std::io::Error> {
+    let list = fs::read_to_string(path)?;
+    let entry = format!("    day{day} => \"bin/{day}.rs\",\n");
+
+    // the list ends with the closing brace of the `registry!` or `days!` invocation.
+    let Some(end) = list.rfind("\n}").map(|i| i + 1) else {
+        return Ok(false);
+    };
+
+    if list.contains(&entry) {
+        return Ok(false);
+    }
+
+    fs::write(path, format!("{}{entry}{}", &list[..end], &list[end..]))?;
+    Ok(true)
+}
+
+/// The template of new solutions, see `scaffold.template` in `aoc.toml`.
+fn read_template() -> String {
+    let Some(path) = &config::get().template else {
+        return MODULE_TEMPLATE.to_string();
+    };
+
+    match fs::read_to_string(path) {
+        Ok(template) => template,
+        Err(e) => {
+            eprintln!("Failed to read template \"{}\": {e}", path.display());
+            process::exit(1);
+        }
+    }
+}
+
 pub fn handle(day: Day, overwrite: bool) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
+    let paths = &config::get().paths;
+    let input_path = paths.inputs.join(format!("{day}.txt"));
+    let input_path = input_path.to_string_lossy();
+    let example_path = paths.examples.join(format!("{day}.txt"));
+    let example_path = example_path.to_string_lossy();
     let module_path = format!("src/bin/{day}.rs");
+    let template = read_template();
 
     let mut file = match safe_create_file(&module_path, overwrite) {
         Ok(file) => file,
@@ -41,7 +81,7 @@ pub fn handle(day: Day, overwrite: bool) {
     };
 
     match file.write_all(
-        MODULE_TEMPLATE
+        template
             .replace("%DAY_NUMBER%", &day.into_inner().to_string())
             .as_bytes(),
     ) {
@@ -74,6 +114,18 @@ pub fn handle(day: Day, overwrite: bool) {
         }
     }
 
+    for path in MODULE_LISTS {
+        match register_module(path, day) {
+            Ok(true) => {
+                println!("Registered module in \"{path}\"");
+            }
+            Ok(false) => {}
+            Err(e) => {
+                eprintln!("Failed to register module in \"{path}\": {e}");
+            }
+        }
+    }
+
     println!("---");
     println!("🎄 Type `cargo solve {day}` to run your solution.");
 }
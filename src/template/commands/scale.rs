@@ -0,0 +1,8 @@
+use crate::template::run_multi::run_bin;
+use crate::template::Day;
+
+pub use crate::template::scale::{Scaling, Strategy};
+
+pub fn handle(day: Day, scaling: Scaling) {
+    run_bin(day, scaling.to_args()).status().unwrap();
+}
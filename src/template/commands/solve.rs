@@ -1,9 +1,40 @@
-use std::process::{Command, Stdio};
+use std::path::Path;
+use std::process::{self, Command, Stdio};
 
-use crate::template::Day;
+use crate::template::run_multi::get_path_for_bin;
+use crate::template::{Days, ANSI_BOLD, ANSI_RESET};
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
+pub use crate::template::runner::parse_part;
+
+#[allow(clippy::too_many_arguments)]
+pub fn handle(
+    days: &Days,
+    release: bool,
+    dhat: bool,
+    allocs: bool,
+    spans: bool,
+    counters: bool,
+    part: Option<u8>,
+    submit_part: Option<u8>,
+) {
+    if dhat && allocs {
+        eprintln!(
+            "`--allocs` can not be combined with `--dhat`, as both replace the global allocator."
+        );
+        process::exit(1);
+    }
+
+    if days.is_empty() {
+        eprintln!("No days to solve.");
+        process::exit(1);
+    }
+
+    if submit_part.is_some() && days.len() > 1 {
+        eprintln!("`--submit` can only be used with a single day.");
+        process::exit(1);
+    }
+
+    let mut cmd_args = vec![];
 
     if dhat {
         cmd_args.extend([
@@ -16,15 +47,57 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
         cmd_args.push("--release".to_string());
     }
 
+    if spans {
+        cmd_args.extend(["--features".to_string(), "spans".to_string()]);
+    }
+
+    // counters are always recorded in debug builds.
+    if counters && (release || dhat) {
+        cmd_args.extend(["--features".to_string(), "counters".to_string()]);
+    }
+
+    if allocs {
+        cmd_args.extend(["--features".to_string(), "allocs".to_string()]);
+    }
+
     cmd_args.push("--".to_string());
 
+    if let Some(part) = part {
+        cmd_args.push("--part".to_string());
+        cmd_args.push(part.to_string());
+    }
+
     if let Some(submit_part) = submit_part {
         cmd_args.push("--submit".to_string());
         cmd_args.push(submit_part.to_string());
     }
 
+    // a single day runs as before, several days get a header each like `cargo all`.
+    if days.len() == 1 {
+        let day = days.iter().next().unwrap().to_string();
+        run(&day, &cmd_args);
+        return;
+    }
+
+    for (i, day) in days.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+        println!("------");
+
+        if Path::new(&get_path_for_bin(day)).exists() {
+            run(&day.to_string(), &cmd_args);
+        } else {
+            println!("Not solved.");
+        }
+    }
+}
+
+fn run(day: &str, cmd_args: &[String]) {
     let mut cmd = Command::new("cargo")
-        .args(&cmd_args)
+        .args(["run", "--bin", day])
+        .args(cmd_args)
         .stdout(Stdio::inherit())
         .stderr(Stdio::inherit())
         .spawn()
@@ -0,0 +1,20 @@
+use crate::template::answers::Answers;
+use crate::template::{readme_stars, ANSI_BOLD, ANSI_RESET};
+
+pub fn handle() {
+    let answers = Answers::read_from_file();
+
+    println!(
+        "{ANSI_BOLD}Stars:{ANSI_RESET} {} / 50",
+        answers.total_stars()
+    );
+
+    match readme_stars::update(&answers) {
+        Ok(()) => {
+            println!("Stored updated star progress.");
+        }
+        Err(_) => {
+            eprintln!("Failed to store updated star progress.");
+        }
+    }
+}
@@ -1,14 +1,53 @@
-use std::collections::HashSet;
+use std::process;
 
+use crate::template::config;
+use crate::template::fingerprint::Fingerprint;
 use crate::template::run_multi::run_multi;
-use crate::template::timings::Timings;
-use crate::template::{all_days, readme_benchmarks, Day};
+use crate::template::timings::{self, Timings};
+use crate::template::{all_days, readme_benchmarks, Days, ANSI_ITALIC, ANSI_RESET};
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
-    let stored_timings = Timings::read_from_file();
+pub use crate::template::isolation::Isolation;
+pub use crate::template::readme_benchmarks::{BarStyle, Columns, SortOrder, TableOptions};
+pub use crate::template::run_multi::Instrumentation;
+pub use crate::template::timings::MergeStrategy;
 
-    let days_to_run = day.map_or_else(
-        || {
+fn read_or_exit(read: fn() -> Result<Timings, timings::Error>, name: &str) -> Timings {
+    match read() {
+        Ok(timings) => timings,
+        Err(e) => {
+            eprintln!("Failed to read {name}: {e}");
+            eprintln!(
+                "Fix or remove the file in `{}` before running `time` again.",
+                config::get().paths.data.display()
+            );
+            process::exit(1);
+        }
+    }
+}
+
+/// Benches `days`, or the days without stored timings if none are given, except the days in `except`.
+#[allow(clippy::too_many_arguments)]
+pub fn handle(
+    days: Option<Days>,
+    except: Option<&Days>,
+    run_all: bool,
+    store: bool,
+    merge: MergeStrategy,
+    instrumentation: Instrumentation,
+    part: Option<u8>,
+    table: &TableOptions,
+) {
+    if store && part.is_some() {
+        eprintln!(
+            "`--part` can not be combined with `--store`, as stored timings cover both parts."
+        );
+        process::exit(1);
+    }
+
+    let stored_timings = read_or_exit(Timings::read_from_file, "stored benchmarks");
+
+    let days_to_run = days
+        .unwrap_or_else(|| {
             if run_all {
                 all_days().collect()
             } else {
@@ -17,18 +56,27 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
                     .filter(|day| !stored_timings.is_day_complete(*day))
                     .collect()
             }
-        },
-        |day| HashSet::from([day]),
-    );
+        })
+        .without(except);
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    let mut timings = run_multi(&days_to_run.0, true, true, instrumentation, part).unwrap();
+
+    let fingerprint = Fingerprint::detect(true);
+    println!("{ANSI_ITALIC}Machine: {fingerprint}{ANSI_RESET}");
+    for timing in &mut timings.data {
+        timing.fingerprint = Some(fingerprint.clone());
+    }
 
     if store {
-        let merged_timings = stored_timings.merge(&timings);
+        let mut history = read_or_exit(Timings::read_history, "benchmark history");
+        history.append_history(&timings);
+        history.store_history().unwrap();
+
+        let merged_timings = stored_timings.merge_with(&timings, merge, &history);
         merged_timings.store_file().unwrap();
 
         println!();
-        match readme_benchmarks::update(merged_timings) {
+        match readme_benchmarks::update(merged_timings, table) {
             Ok(()) => {
                 println!("Stored updated benchmarks.");
             }
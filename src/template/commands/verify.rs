@@ -0,0 +1,25 @@
+use std::process;
+
+use crate::template::{all_days, verify::verify, Day};
+
+pub fn handle(day: Option<Day>, corpus: bool) {
+    let days: Vec<Day> = match day {
+        Some(day) => vec![day],
+        None => all_days().collect(),
+    };
+
+    match verify(&days, corpus) {
+        Ok(failures) if failures.is_empty() => {}
+        Ok(failures) => {
+            println!("Failing inputs:");
+            for (day, inputs) in failures {
+                println!("  Day {day}: {}", inputs.join(", "));
+            }
+            process::exit(1);
+        }
+        Err(e) => {
+            eprintln!("Failed to verify solutions: {e}");
+            process::exit(1);
+        }
+    }
+}
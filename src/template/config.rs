@@ -0,0 +1,457 @@
+/// Module for the project configuration in `aoc.toml`, see [`Config`].
+/// Every setting can be overridden by an environment variable named after its key,
+/// e.g. `AOC_YEAR` for `year`, or `AOC_BENCH_BUDGET_MS` for `budget_ms` in `[bench]`.
+use std::{
+    collections::HashMap,
+    env, fs, io,
+    path::{Path, PathBuf},
+    process,
+    str::FromStr,
+    sync::OnceLock,
+    time::Duration,
+};
+
+use crate::template::report::Format;
+
+static CONFIG_FILE_PATH: &str = "aoc.toml";
+
+/// Type of the value of a setting.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Kind {
+    String,
+    Integer,
+}
+
+/// Every setting, by `section.key`.
+const KEYS: [(&str, Kind); 12] = [
+    ("year", Kind::Integer),
+    ("user_id", Kind::Integer),
+    ("paths.data", Kind::String),
+    ("paths.inputs", Kind::String),
+    ("paths.examples", Kind::String),
+    ("paths.puzzles", Kind::String),
+    ("bench.budget_ms", Kind::Integer),
+    ("bench.min_batches", Kind::Integer),
+    ("bench.max_batches", Kind::Integer),
+    ("scaffold.template", Kind::String),
+    ("timeouts.aoc_cli_secs", Kind::Integer),
+    ("output.format", Kind::String),
+];
+
+/// Where the data of the template lives.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Paths {
+    /// Root of the stored timings, answers, reports and generated inputs.
+    pub data: PathBuf,
+    pub inputs: PathBuf,
+    pub examples: PathBuf,
+    pub puzzles: PathBuf,
+}
+
+impl Paths {
+    /// Directory of `folder`, e.g. `inputs`. Folders without their own setting live in `data`.
+    pub fn folder(&self, folder: &str) -> PathBuf {
+        match folder {
+            "inputs" => self.inputs.clone(),
+            "examples" => self.examples.clone(),
+            "puzzles" => self.puzzles.clone(),
+            _ => self.data.join(folder),
+        }
+    }
+}
+
+/// How long `cargo time` benches each part.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Bench {
+    pub budget: Duration,
+    /// Number of timed batches is clamped to `min_batches..=max_batches`.
+    pub min_batches: u128,
+    pub max_batches: u128,
+}
+
+/// Settings of the template, read from `aoc.toml`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Config {
+    /// Year of the puzzles, passed to aoc-cli. Defaults to the latest year.
+    pub year: Option<u16>,
+    /// Id of the Advent of Code account, as shown on the settings page of adventofcode.com.
+    pub user_id: Option<u64>,
+    pub paths: Paths,
+    pub bench: Bench,
+    /// Template of new solutions, see `cargo scaffold`. Defaults to `src/template.txt`.
+    pub template: Option<PathBuf>,
+    /// Calls of aoc-cli are killed after this duration.
+    pub aoc_cli_timeout: Option<Duration>,
+    /// Default format of `cargo report`.
+    pub format: Format,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        let data = PathBuf::from("data");
+        Config {
+            year: None,
+            user_id: None,
+            paths: Paths {
+                inputs: data.join("inputs"),
+                examples: data.join("examples"),
+                puzzles: data.join("puzzles"),
+                data,
+            },
+            bench: Bench {
+                budget: Duration::from_secs(1),
+                min_batches: 10,
+                max_batches: 10000,
+            },
+            template: None,
+            aoc_cli_timeout: None,
+            format: Format::Markdown,
+        }
+    }
+}
+
+/// Returns the configuration, read on first use. Exits if it is invalid.
+pub fn get() -> &'static Config {
+    static CONFIG: OnceLock<Config> = OnceLock::new();
+
+    CONFIG.get_or_init(|| match Config::read_from_file() {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("Invalid configuration: {e}");
+            process::exit(1);
+        }
+    })
+}
+
+impl Config {
+    /// Reads `aoc.toml` if present and applies the environment overrides.
+    pub fn read_from_file() -> Result<Self, String> {
+        let text = match fs::read_to_string(CONFIG_FILE_PATH) {
+            Ok(text) => text,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(format!("{CONFIG_FILE_PATH}: {e}")),
+        };
+
+        let config = Config::parse(&text, |name| env::var(name).ok())?;
+
+        if let Some(template) = &config.template {
+            if !template.exists() {
+                return Err(format!(
+                    "scaffold.template: \"{}\" does not exist.",
+                    template.display()
+                ));
+            }
+        }
+
+        Ok(config)
+    }
+
+    /// Parses the contents of `aoc.toml`, overridden by the variables that `env` returns.
+    fn parse(text: &str, env: impl Fn(&str) -> Option<String>) -> Result<Self, String> {
+        let mut values = parse_toml(text)?;
+
+        for (key, kind) in KEYS {
+            let name = env_name(key);
+            if let Some(value) = env(&name) {
+                let value = match kind {
+                    Kind::String => Value::String(value),
+                    Kind::Integer => Value::Integer(
+                        value
+                            .parse()
+                            .map_err(|_| format!("{name}: expected an integer, got `{value}`."))?,
+                    ),
+                };
+                values.insert(key.to_string(), (Source::Env(name), value));
+            }
+        }
+
+        Settings(values).into_config()
+    }
+}
+
+/// Name of the environment variable that overrides `key`.
+fn env_name(key: &str) -> String {
+    format!("AOC_{}", key.replace('.', "_").to_uppercase())
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Value {
+    String(String),
+    Integer(i64),
+}
+
+/// Where a setting was read from, for error messages.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Source {
+    Line(usize),
+    Env(String),
+}
+
+impl Source {
+    fn describe(&self, key: &str) -> String {
+        match self {
+            Source::Line(line) => format!("{CONFIG_FILE_PATH}:{line}: `{key}`"),
+            Source::Env(name) => name.clone(),
+        }
+    }
+}
+
+/// Parses the subset of TOML that the configuration uses: tables, comments,
+/// and keys with string or integer values. Keys are checked against [`KEYS`].
+fn parse_toml(text: &str) -> Result<HashMap<String, (Source, Value)>, String> {
+    let mut values = HashMap::new();
+    let mut table = String::new();
+
+    for (i, line) in text.lines().enumerate() {
+        let number = i + 1;
+        let error = |message: String| format!("{CONFIG_FILE_PATH}:{number}: {message}");
+
+        let line = strip_comment(line).trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        if let Some(name) = line.strip_prefix('[') {
+            let name = name
+                .strip_suffix(']')
+                .ok_or_else(|| error(format!("expected `]` in `{line}`.")))?;
+            table = name.trim().to_string();
+            continue;
+        }
+
+        let (key, value) = line
+            .split_once('=')
+            .ok_or_else(|| error(format!("expected `key = value`, got `{line}`.")))?;
+        let key = key.trim();
+        let key = if table.is_empty() {
+            key.to_string()
+        } else {
+            format!("{table}.{key}")
+        };
+
+        let Some(&(_, kind)) = KEYS.iter().find(|(k, _)| *k == key) else {
+            return Err(error(format!("unknown setting `{key}`.")));
+        };
+
+        let value = value.trim();
+        let value = match kind {
+            Kind::String => value
+                .strip_prefix('"')
+                .and_then(|v| v.strip_suffix('"'))
+                .map(|v| Value::String(v.replace("\\\\", "\\").replace("\\\"", "\"")))
+                .ok_or_else(|| error(format!("`{key}` expects a quoted string, got `{value}`.")))?,
+            Kind::Integer => value
+                .replace('_', "")
+                .parse()
+                .map(Value::Integer)
+                .map_err(|_| error(format!("`{key}` expects an integer, got `{value}`.")))?,
+        };
+
+        if values
+            .insert(key.clone(), (Source::Line(number), value))
+            .is_some()
+        {
+            return Err(error(format!("`{key}` is set twice.")));
+        }
+    }
+
+    Ok(values)
+}
+
+/// Removes a `#` comment, unless it is part of a string.
+fn strip_comment(line: &str) -> &str {
+    let mut in_string = false;
+    for (i, c) in line.char_indices() {
+        match c {
+            '"' => in_string = !in_string,
+            '#' if !in_string => return &line[..i],
+            _ => {}
+        }
+    }
+    line
+}
+
+/// Parsed settings, converted to a [`Config`] with validation.
+struct Settings(HashMap<String, (Source, Value)>);
+
+impl Settings {
+    fn string(&self, key: &str) -> Option<(&Source, &str)> {
+        match self.0.get(key) {
+            Some((source, Value::String(value))) => Some((source, value)),
+            _ => None,
+        }
+    }
+
+    /// An integer in `range`.
+    fn integer<T: TryFrom<i64>>(
+        &self,
+        key: &str,
+        range: std::ops::RangeInclusive<i64>,
+    ) -> Result<Option<T>, String> {
+        match self.0.get(key) {
+            Some((source, Value::Integer(value))) => {
+                if !range.contains(value) {
+                    return Err(format!(
+                        "{} must be between {} and {}, got {value}.",
+                        source.describe(key),
+                        range.start(),
+                        range.end()
+                    ));
+                }
+                Ok(T::try_from(*value).ok())
+            }
+            _ => Ok(None),
+        }
+    }
+
+    fn path(&self, key: &str) -> Result<Option<PathBuf>, String> {
+        match self.string(key) {
+            Some((source, "")) => Err(format!("{} must not be empty.", source.describe(key))),
+            Some((_, value)) => Ok(Some(PathBuf::from(value))),
+            None => Ok(None),
+        }
+    }
+
+    fn into_config(self) -> Result<Config, String> {
+        let defaults = Config::default();
+
+        let data = self.path("paths.data")?.unwrap_or(defaults.paths.data);
+        let folder = |key: &str, name: &str| -> Result<PathBuf, String> {
+            Ok(self
+                .path(key)?
+                .unwrap_or_else(|| Path::new(&data).join(name)))
+        };
+        let paths = Paths {
+            inputs: folder("paths.inputs", "inputs")?,
+            examples: folder("paths.examples", "examples")?,
+            puzzles: folder("paths.puzzles", "puzzles")?,
+            data: data.clone(),
+        };
+
+        let bench = Bench {
+            budget: self
+                .integer("bench.budget_ms", 1..=600_000)?
+                .map_or(defaults.bench.budget, Duration::from_millis),
+            min_batches: self
+                .integer("bench.min_batches", 1..=1_000_000)?
+                .unwrap_or(defaults.bench.min_batches),
+            max_batches: self
+                .integer("bench.max_batches", 1..=1_000_000)?
+                .unwrap_or(defaults.bench.max_batches),
+        };
+        if bench.min_batches > bench.max_batches {
+            return Err(format!(
+                "bench.min_batches ({}) must not be larger than bench.max_batches ({}).",
+                bench.min_batches, bench.max_batches
+            ));
+        }
+
+        let format = match self.string("output.format") {
+            Some((source, value)) => Format::from_str(value)
+                .map_err(|e| format!("{}: {e}", source.describe("output.format")))?,
+            None => defaults.format,
+        };
+
+        Ok(Config {
+            year: self.integer("year", 2015..=2100)?,
+            user_id: self.integer("user_id", 1..=i64::MAX)?,
+            paths,
+            bench,
+            template: self.path("scaffold.template")?,
+            aoc_cli_timeout: self
+                .integer("timeouts.aoc_cli_secs", 1..=3600)?
+                .map(Duration::from_secs),
+            format,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Config;
+    use crate::template::report::Format;
+    use std::{path::PathBuf, time::Duration};
+
+    fn parse(text: &str) -> Result<Config, String> {
+        Config::parse(text, |_| None)
+    }
+
+    #[test]
+    fn parses_config() {
+        let text = r#"
+            year = 2023 # the year of the puzzles
+            user_id = 3031
+
+            [paths]
+            data = "aoc/data"
+            examples = "aoc/#examples"
+
+            [bench]
+            budget_ms = 2_000
+            max_batches = 500
+
+            [output]
+            format = "csv"
+        "#;
+        let config = parse(text).unwrap();
+
+        assert_eq!(config.year, Some(2023));
+        assert_eq!(config.user_id, Some(3031));
+        assert_eq!(config.paths.inputs, PathBuf::from("aoc/data/inputs"));
+        assert_eq!(config.paths.examples, PathBuf::from("aoc/#examples"));
+        assert_eq!(
+            config.paths.folder("generated"),
+            PathBuf::from("aoc/data/generated")
+        );
+        assert_eq!(config.bench.budget, Duration::from_secs(2));
+        assert_eq!(config.bench.min_batches, 10);
+        assert_eq!(config.bench.max_batches, 500);
+        assert_eq!(config.format, Format::Csv);
+
+        assert_eq!(parse("").unwrap(), Config::default());
+    }
+
+    #[test]
+    fn rejects_invalid_config() {
+        assert_eq!(
+            parse("[bench]\nbudget = 1\n"),
+            Err("aoc.toml:2: unknown setting `bench.budget`.".into())
+        );
+        assert_eq!(
+            parse("year = \"2024\"\n"),
+            Err("aoc.toml:1: `year` expects an integer, got `\"2024\"`.".into())
+        );
+        assert_eq!(
+            parse("\n\nyear = 1999\n"),
+            Err("aoc.toml:3: `year` must be between 2015 and 2100, got 1999.".into())
+        );
+        assert_eq!(
+            parse("[bench]\nmin_batches = 20\nmax_batches = 10\n"),
+            Err("bench.min_batches (20) must not be larger than bench.max_batches (10).".into())
+        );
+    }
+
+    #[test]
+    fn applies_env_overrides() {
+        let env = |name: &str| match name {
+            "AOC_YEAR" => Some("2022".to_string()),
+            "AOC_USER_ID" => Some("1461419".to_string()),
+            "AOC_PATHS_INPUTS" => Some("/tmp/inputs".to_string()),
+            _ => None,
+        };
+        let config = Config::parse("year = 2023\n", env).unwrap();
+        assert_eq!(config.year, Some(2022));
+        assert_eq!(config.user_id, Some(1461419));
+        assert_eq!(config.paths.inputs, PathBuf::from("/tmp/inputs"));
+
+        let env = |name: &str| (name == "AOC_BENCH_BUDGET_MS").then(|| "0".to_string());
+        assert_eq!(
+            Config::parse("", env),
+            Err("AOC_BENCH_BUDGET_MS must be between 1 and 600000, got 0.".into())
+        );
+    }
+}
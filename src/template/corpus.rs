@@ -0,0 +1,161 @@
+/// Module for the corpus of inputs shared by other users, see `cargo verify --corpus`.
+/// Each variant lives in `data/inputs/<name>/`, next to an `answers.txt` with its known answers:
+///
+/// ```text
+/// # day  part 1   part 2
+/// 01     1830467  26674158
+/// 17     4,6,3,5  -
+/// ```
+use std::{collections::HashMap, fs, path::PathBuf, str::FromStr};
+
+use crate::template::{config, Day};
+
+static ANSWERS_FILE_NAME: &str = "answers.txt";
+
+/// Known answers for one day of a variant. `None` if the answer of a part is not known.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct KnownAnswers {
+    pub part_1: Option<String>,
+    pub part_2: Option<String>,
+}
+
+impl KnownAnswers {
+    pub fn get(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part_1.as_deref(),
+            2 => self.part_2.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+/// Inputs of another user, with their known answers.
+#[derive(Clone, Debug)]
+pub struct Variant {
+    pub name: String,
+    dir: PathBuf,
+    answers: HashMap<Day, KnownAnswers>,
+}
+
+impl Variant {
+    /// Path of the variant's input for a day, if it has one.
+    pub fn input_path(&self, day: Day) -> Option<PathBuf> {
+        let path = self.dir.join(format!("{day}.txt"));
+        path.exists().then_some(path)
+    }
+
+    pub fn answers(&self, day: Day) -> KnownAnswers {
+        self.answers.get(&day).cloned().unwrap_or_default()
+    }
+}
+
+/// All variants in `data/inputs/`, sorted by name.
+#[derive(Clone, Debug, Default)]
+pub struct Corpus {
+    pub variants: Vec<Variant>,
+}
+
+impl Corpus {
+    /// Reads every sub-directory of the inputs directory as a variant.
+    /// A variant without an answers file is still run, but can not fail.
+    pub fn read_from_dir() -> Result<Self, String> {
+        let Ok(entries) = fs::read_dir(&config::get().paths.inputs) else {
+            return Ok(Corpus::default());
+        };
+
+        let mut variants = vec![];
+
+        for entry in entries.flatten() {
+            let dir = entry.path();
+            if !dir.is_dir() {
+                continue;
+            }
+
+            let name = entry.file_name().to_string_lossy().to_string();
+            let answers_path = dir.join(ANSWERS_FILE_NAME);
+            let answers = match fs::read_to_string(&answers_path) {
+                Ok(text) => {
+                    parse_answers(&text).map_err(|e| format!("{}: {e}", answers_path.display()))?
+                }
+                Err(_) => HashMap::new(),
+            };
+
+            variants.push(Variant { name, dir, answers });
+        }
+
+        variants.sort_unstable_by(|a, b| a.name.cmp(&b.name));
+        Ok(Corpus { variants })
+    }
+}
+
+/// Parses an answers file. Blank lines and lines starting with `#` are ignored, `-` marks an unknown answer.
+fn parse_answers(text: &str) -> Result<HashMap<Day, KnownAnswers>, String> {
+    let mut answers = HashMap::new();
+
+    for (i, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        let [day, part_1, part_2] = fields[..] else {
+            return Err(format!(
+                "line {}: expected `<day> <part 1> <part 2>`, got `{line}`.",
+                i + 1
+            ));
+        };
+
+        let day = Day::from_str(day)
+            .map_err(|_| format!("line {}: `{day}` is not a valid day.", i + 1))?;
+        let known = |answer: &str| (answer != "-").then(|| answer.to_string());
+
+        answers.insert(
+            day,
+            KnownAnswers {
+                part_1: known(part_1),
+                part_2: known(part_2),
+            },
+        );
+    }
+
+    Ok(answers)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_answers, KnownAnswers};
+    use crate::day;
+
+    #[test]
+    fn parses_answers() {
+        let text = "# day part_1 part_2\n01 11 31\n\n17 4,6,3,5 -\n";
+        let answers = parse_answers(text).unwrap();
+
+        assert_eq!(answers.len(), 2);
+        assert_eq!(
+            answers[&day!(1)],
+            KnownAnswers {
+                part_1: Some("11".into()),
+                part_2: Some("31".into()),
+            }
+        );
+        assert_eq!(answers[&day!(17)].get(1), Some("4,6,3,5"));
+        assert_eq!(answers[&day!(17)].get(2), None);
+        assert_eq!(answers[&day!(1)].get(3), None);
+    }
+
+    #[test]
+    fn rejects_malformed_answers() {
+        assert_eq!(
+            parse_answers("01 11\n"),
+            Err("line 1: expected `<day> <part 1> <part 2>`, got `01 11`.".into())
+        );
+        assert_eq!(
+            parse_answers("# header\n26 1 2\n"),
+            Err("line 2: `26` is not a valid day.".into())
+        );
+    }
+}
@@ -0,0 +1,148 @@
+/// Module that counts named events of a solution, see [`crate::aoc_count`].
+/// Counters are recorded in debug builds and in builds with the `counters` feature,
+/// otherwise they compile to nothing.
+use std::fmt::Display;
+
+/// Adds to a named counter, by one or by the given amount.
+///
+/// ```ignore
+/// aoc_count!("heap_pops");
+/// aoc_count!("neighbours", next.len());
+/// ```
+#[macro_export]
+macro_rules! aoc_count {
+    ($name:expr) => {
+        $crate::template::counters::add($name, 1)
+    };
+    ($name:expr, $n:expr) => {
+        $crate::template::counters::add($name, u64::try_from($n).unwrap_or(u64::MAX))
+    };
+}
+
+/// Whether counters are recorded in this build.
+pub const ENABLED: bool = cfg!(any(debug_assertions, feature = "counters"));
+
+#[cfg(not(any(debug_assertions, feature = "counters")))]
+#[inline]
+pub fn add(_name: &'static str, _n: u64) {}
+
+/// Runs `f`. Counters are not recorded in this build.
+#[cfg(not(any(debug_assertions, feature = "counters")))]
+pub fn record<T>(f: impl FnOnce() -> T) -> (T, Counts) {
+    (f(), Counts::default())
+}
+
+#[cfg(any(debug_assertions, feature = "counters"))]
+pub use recording::{add, record};
+
+#[cfg(any(debug_assertions, feature = "counters"))]
+mod recording {
+    use super::Counts;
+    use std::cell::RefCell;
+
+    thread_local! {
+        static COUNTS: RefCell<Option<Vec<(&'static str, u64)>>> = const { RefCell::new(None) };
+    }
+
+    pub fn add(name: &'static str, n: u64) {
+        COUNTS.with(|c| {
+            if let Some(counts) = c.borrow_mut().as_mut() {
+                match counts.iter_mut().find(|(k, _)| *k == name) {
+                    Some((_, value)) => *value = value.saturating_add(n),
+                    None => counts.push((name, n)),
+                }
+            }
+        });
+    }
+
+    /// Runs `f` and records the counters it bumps on the current thread.
+    pub fn record<T>(f: impl FnOnce() -> T) -> (T, Counts) {
+        COUNTS.with(|c| *c.borrow_mut() = Some(vec![]));
+        let result = f();
+        let counts = COUNTS.with(|c| c.borrow_mut().take()).unwrap_or_default();
+        let counts = Counts(
+            counts
+                .into_iter()
+                .map(|(name, value)| (name.to_string(), value))
+                .collect(),
+        );
+        (result, counts)
+    }
+}
+
+/// Counter values of a solution part, in the order the counters were first bumped.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Counts(pub Vec<(String, u64)>);
+
+/// Prefix of the line that reports the counters of a part.
+const PREFIX: &str = "  counters: ";
+
+impl Counts {
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Parses a line written by the `Display` implementation.
+    pub fn parse(line: &str) -> Option<Self> {
+        line.strip_prefix(PREFIX)?
+            .split(", ")
+            .map(|pair| {
+                let (name, value) = pair.split_once('=')?;
+                Some((name.to_string(), value.parse().ok()?))
+            })
+            .collect::<Option<_>>()
+            .map(Counts)
+    }
+}
+
+impl Display for Counts {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{PREFIX}")?;
+        for (i, (name, value)) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{name}={value}")?;
+        }
+        Ok(())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Counts;
+
+    #[test]
+    fn round_trips_display() {
+        let counts = Counts(vec![("heap_pops".into(), 12345), ("memo_hits".into(), 0)]);
+        assert_eq!(
+            counts.to_string(),
+            "  counters: heap_pops=12345, memo_hits=0"
+        );
+        assert_eq!(Counts::parse(&counts.to_string()), Some(counts));
+        assert_eq!(Counts::parse("Part 1: 12 (1.0ms)"), None);
+        assert_eq!(Counts::parse("  counters: heap_pops"), None);
+    }
+
+    #[test]
+    fn records_counters_in_debug_builds() {
+        let (result, counts) = super::record(|| {
+            crate::aoc_count!("pops");
+            crate::aoc_count!("pops", 2_usize);
+            crate::aoc_count!("hits");
+            7
+        });
+        assert_eq!(result, 7);
+        if super::ENABLED {
+            assert_eq!(counts, Counts(vec![("pops".into(), 3), ("hits".into(), 1)]));
+        } else {
+            assert!(counts.is_empty());
+        }
+
+        // counters outside of a recording are dropped.
+        crate::aoc_count!("pops");
+        assert!(super::record(|| ()).1.is_empty());
+    }
+}
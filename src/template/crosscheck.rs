@@ -0,0 +1,461 @@
+/// Module that compares alternative implementations of a part, see `cargo crosscheck`.
+/// Alternatives are registered with `solution!(9, alternatives = [2 => part_two_spans])`.
+use std::{
+    env,
+    fmt::Display,
+    fs,
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+use crate::template::config;
+use crate::template::generate::{self, Generator, Rng};
+use crate::template::registry::Implementation;
+use crate::template::runner::{catch_silently, mean_call_time};
+use crate::template::solution;
+use crate::template::{Day, ANSI_BOLD, ANSI_RESET};
+
+/// Time spent benching each implementation.
+const BUDGET_PER_IMPLEMENTATION: Duration = Duration::from_millis(500);
+
+/// Maximum number of candidate inputs tried while shrinking a disagreement.
+const MAX_SHRINK_RUNS: usize = 2000;
+
+/// Shrunk inputs up to this size are printed in full.
+const MAX_PRINTED_BYTES: usize = 200;
+
+/// Runs one implementation, catching panics.
+fn run(implementation: &Implementation, input: &str) -> Outcome {
+    match catch_silently(|| implementation.solve(input)) {
+        Some(solution::Outcome::Solved(answer)) => Outcome::Answer(answer),
+        Some(solution::Outcome::Unsolved) => Outcome::Unsolved,
+        Some(solution::Outcome::Failed(error)) => Outcome::Failed(error.to_string()),
+        None => Outcome::Panicked,
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Outcome {
+    Answer(String),
+    Unsolved,
+    /// The implementation returned an error about the input.
+    Failed(String),
+    Panicked,
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Outcome::Answer(answer) => write!(f, "{answer}"),
+            Outcome::Unsolved => write!(f, "✖"),
+            Outcome::Failed(error) => write!(f, "error: {error}"),
+            Outcome::Panicked => write!(f, "panicked"),
+        }
+    }
+}
+
+/// Outcome of every implementation of a part on one input.
+#[derive(Debug, PartialEq, Eq)]
+struct Outcomes(Vec<(&'static str, Outcome)>);
+
+impl Outcomes {
+    fn collect(implementations: &[&Implementation], input: &str) -> Self {
+        Outcomes(
+            implementations
+                .iter()
+                .map(|i| (i.name, run(i, input)))
+                .collect(),
+        )
+    }
+
+    fn agree(&self) -> bool {
+        self.0.windows(2).all(|w| w[0].1 == w[1].1)
+    }
+
+    fn any_panicked(&self) -> bool {
+        self.0.iter().any(|(_, o)| *o == Outcome::Panicked)
+    }
+}
+
+impl Display for Outcomes {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.agree() {
+            return match self.0.first() {
+                Some((_, outcome)) => write!(f, "{outcome}"),
+                None => Ok(()),
+            };
+        }
+
+        let outcomes: Vec<String> = self
+            .0
+            .iter()
+            .map(|(name, outcome)| format!("{name} = {outcome}"))
+            .collect();
+        write!(f, "{}", outcomes.join(", "))
+    }
+}
+
+/// Input the implementations are compared on.
+struct Input {
+    name: String,
+    text: String,
+}
+
+/// Crosscheck requested by `cargo crosscheck`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Crosscheck {
+    /// Number of inputs generated with the day's generator, if it has one.
+    pub seeds: u64,
+}
+
+impl Default for Crosscheck {
+    fn default() -> Self {
+        Crosscheck { seeds: 10 }
+    }
+}
+
+impl Crosscheck {
+    /// Arguments that forward the crosscheck to a solution binary.
+    pub fn to_args(self) -> Vec<String> {
+        vec![
+            "--crosscheck".into(),
+            "--seeds".into(),
+            self.seeds.to_string(),
+        ]
+    }
+
+    /// Reads the crosscheck forwarded by [`Crosscheck::to_args`] from the command-line.
+    pub fn from_args() -> Option<Self> {
+        let args: Vec<String> = env::args().collect();
+        Self::parse_args(&args)
+    }
+
+    fn parse_args(args: &[String]) -> Option<Self> {
+        if !args.iter().any(|x| x == "--crosscheck") {
+            return None;
+        }
+
+        let seeds = args
+            .iter()
+            .position(|x| x == "--seeds")
+            .and_then(|i| args.get(i + 1))
+            .and_then(|x| x.parse().ok())
+            .unwrap_or(Crosscheck::default().seeds);
+
+        Some(Crosscheck { seeds })
+    }
+
+    /// Compares the implementations of every part that has alternatives, then benches them.
+    pub fn run(
+        self,
+        day: Day,
+        implementations: &[Implementation],
+        generator: Option<&dyn Generator>,
+    ) {
+        let inputs = self.collect_inputs(day, generator);
+
+        for part in [1, 2] {
+            let candidates: Vec<&Implementation> =
+                implementations.iter().filter(|i| i.part == part).collect();
+
+            if candidates.len() < 2 {
+                continue;
+            }
+
+            println!(
+                "{ANSI_BOLD}Part {part}{ANSI_RESET}: {} implementations, {} inputs",
+                candidates.len(),
+                inputs.len()
+            );
+
+            let mut agreed_on = vec![];
+            let mut disagreed = false;
+            for input in &inputs {
+                let outcomes = Outcomes::collect(&candidates, &input.text);
+                if outcomes.agree() {
+                    println!("  ✔ {}: {outcomes}", input.name);
+                    agreed_on.push(input);
+                } else {
+                    println!("  ✖ {}: {outcomes}", input.name);
+                    // shrinking is slow, and one minimal input is usually enough to find the bug.
+                    if !disagreed {
+                        report_shrunk(day, part, &candidates, &input.text, &outcomes);
+                        disagreed = true;
+                    }
+                }
+            }
+
+            // bench on the real input if possible, otherwise on the largest input all implementations agree on.
+            let bench_input = agreed_on
+                .iter()
+                .find(|i| i.name.starts_with("inputs/"))
+                .or_else(|| agreed_on.iter().max_by_key(|i| i.text.len()));
+
+            if let Some(input) = bench_input {
+                println!("  Benchmark ({}):", input.name);
+                bench(&candidates, &input.text);
+            }
+            println!();
+        }
+
+        if !implementations
+            .iter()
+            .any(|i| implementations.iter().filter(|j| j.part == i.part).count() > 1)
+        {
+            println!(
+                "Day {day} has no alternative implementations. Register them with `solution!({}, alternatives = [2 => part_two_alt])`.",
+                day.into_inner()
+            );
+        }
+    }
+
+    /// Real input, examples, previously generated inputs and freshly generated ones.
+    #[allow(clippy::cast_possible_truncation)]
+    fn collect_inputs(self, day: Day, generator: Option<&dyn Generator>) -> Vec<Input> {
+        let mut inputs = vec![];
+
+        let paths = &config::get().paths;
+        let data = paths.data.as_path();
+        let input_path = paths.inputs.join(format!("{day}.txt"));
+        inputs.extend(read_input(&input_path, data));
+
+        // examples are named `<day>.txt`, or `<day>-<n>.txt` for additional ones.
+        let mut examples = list_files(&paths.examples);
+        examples.retain(|path| {
+            path.file_stem()
+                .and_then(|s| s.to_str())
+                .is_some_and(|s| s == day.to_string() || s.starts_with(&format!("{day}-")))
+        });
+        inputs.extend(examples.iter().filter_map(|path| read_input(path, data)));
+
+        let generated = list_files(&generate::get_path(day, 0).with_file_name(""));
+        inputs.extend(generated.iter().filter_map(|path| read_input(path, data)));
+
+        if let Some(generator) = generator {
+            let full = generator.default_size() as u64;
+            for seed in 1..=self.seeds {
+                // grow towards the full size, so that small disagreements are found first.
+                let size = (full * seed / self.seeds).max(1) as usize;
+                inputs.push(Input {
+                    name: format!("seed {seed} (size {size})"),
+                    text: generator.generate(&mut Rng::new(seed), size),
+                });
+            }
+        }
+
+        inputs
+    }
+}
+
+fn list_files(dir: &Path) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| Some(entry.ok()?.path()))
+        .filter(|path| path.extension().is_some_and(|e| e == "txt"))
+        .collect();
+    files.sort();
+    files
+}
+
+/// Reads a non-empty input, named by its path relative to `data/`.
+fn read_input(path: &Path, data: &Path) -> Option<Input> {
+    let text = fs::read_to_string(path)
+        .ok()
+        .filter(|t| !t.trim().is_empty())?;
+    Some(Input {
+        name: path
+            .strip_prefix(data)
+            .unwrap_or(path)
+            .display()
+            .to_string(),
+        text,
+    })
+}
+
+/// Shrinks a disagreement and reports the smallest input that still shows it.
+fn report_shrunk(
+    day: Day,
+    part: u8,
+    implementations: &[&Implementation],
+    input: &str,
+    outcomes: &Outcomes,
+) {
+    // unless the original input made an implementation panic, only accept valid candidates,
+    // so that the disagreement is not replaced by a parsing error of a broken input.
+    let allow_panics = outcomes.any_panicked();
+    let disagrees = |candidate: &str| {
+        let outcomes = Outcomes::collect(implementations, candidate);
+        !outcomes.agree() && (allow_panics || !outcomes.any_panicked())
+    };
+
+    let shrunk = shrink(input, disagrees);
+    let outcomes = Outcomes::collect(implementations, &shrunk);
+
+    // written next to the generated inputs, so that later crosschecks keep comparing on it.
+    let path = generate::get_path(day, 0).with_file_name(format!("shrunk-{part}.txt"));
+    let written = path
+        .parent()
+        .is_some_and(|dir| fs::create_dir_all(dir).is_ok())
+        && fs::write(&path, &shrunk).is_ok();
+
+    if written {
+        println!(
+            "    shrunk to {} B: {outcomes}, written to {}",
+            shrunk.len(),
+            path.display()
+        );
+    } else {
+        println!("    shrunk to {} B: {outcomes}", shrunk.len());
+    }
+
+    if shrunk.len() <= MAX_PRINTED_BYTES {
+        for line in shrunk.lines() {
+            println!("    │ {line}");
+        }
+    }
+}
+
+/// Removes lines, then characters, from `input` as long as `fails` holds for the result.
+fn shrink(input: &str, fails: impl Fn(&str) -> bool) -> String {
+    let mut runs = 0;
+    let mut fails = |candidate: &str| {
+        runs += 1;
+        runs <= MAX_SHRINK_RUNS && fails(candidate)
+    };
+
+    let lines: Vec<String> = input.lines().map(str::to_string).collect();
+    let lines = shrink_units(lines, "\n", &mut fails);
+
+    let chars: Vec<String> = lines.join("\n").chars().map(String::from).collect();
+    let chars = shrink_units(chars, "", &mut fails);
+
+    let mut shrunk = chars.concat();
+    if input.ends_with('\n') && !shrunk.ends_with('\n') {
+        shrunk.push('\n');
+    }
+    shrunk
+}
+
+/// Removes chunks of `units`, halving the chunk size down to single units.
+fn shrink_units(
+    mut units: Vec<String>,
+    separator: &str,
+    fails: &mut impl FnMut(&str) -> bool,
+) -> Vec<String> {
+    let mut chunk = units.len() / 2;
+
+    while chunk > 0 {
+        let mut start = 0;
+        while start < units.len() {
+            let end = (start + chunk).min(units.len());
+            let candidate: Vec<String> = units[..start]
+                .iter()
+                .chain(&units[end..])
+                .cloned()
+                .collect();
+
+            if !candidate.is_empty() && fails(&(candidate.join(separator) + "\n")) {
+                units = candidate;
+            } else {
+                start += chunk;
+            }
+        }
+        chunk /= 2;
+    }
+
+    units
+}
+
+/// Benches every implementation on `input`, relative to the fastest one.
+#[allow(clippy::cast_precision_loss)]
+fn bench(implementations: &[&Implementation], input: &str) {
+    let timings: Vec<(&str, Duration)> = implementations
+        .iter()
+        .map(|i| {
+            let timer = Instant::now();
+            let _ = i.solve(input);
+            let base_time = timer.elapsed();
+            let solve = |input| i.solve(input);
+            let mean = mean_call_time(&solve, input, &base_time, BUDGET_PER_IMPLEMENTATION);
+            (i.name, mean)
+        })
+        .collect();
+
+    let fastest = timings.iter().map(|(_, t)| *t).min().unwrap_or_default();
+    let width = timings.iter().map(|(n, _)| n.len()).max().unwrap_or(0);
+
+    for (name, time) in &timings {
+        let ratio = time.as_nanos() as f64 / fastest.as_nanos().max(1) as f64;
+        println!(
+            "    {name:<width$}  {:>10}  {ratio:>6.2}×",
+            format!("{time:.1?}")
+        );
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{run, shrink, Crosscheck, Implementation, Outcome, Outcomes};
+    use crate::template::parse::ParseExt;
+
+    fn sum(input: &str) -> Option<u64> {
+        input.lines().map(|l| l.parse::<u64>().ok()).sum()
+    }
+
+    /// Wrong as soon as a line is larger than 9.
+    fn sum_digits(input: &str) -> Option<u64> {
+        input
+            .lines()
+            .map(|l| {
+                l.chars()
+                    .map(|c| c.to_digit(10).map(u64::from))
+                    .sum::<Option<u64>>()
+            })
+            .sum()
+    }
+
+    #[test]
+    fn compares_outcomes() {
+        let implementations = [
+            Implementation::new(1, "sum", sum),
+            Implementation::new(1, "sum_digits", sum_digits),
+        ];
+        let implementations: Vec<&Implementation> = implementations.iter().collect();
+
+        let outcomes = Outcomes::collect(&implementations, "1\n2\n3\n");
+        assert!(outcomes.agree());
+        assert_eq!(outcomes.to_string(), "6");
+
+        let outcomes = Outcomes::collect(&implementations, "1\n12\n");
+        assert!(!outcomes.agree());
+        assert_eq!(outcomes.to_string(), "sum = 13, sum_digits = 4");
+
+        let panics = Implementation::new(1, "panics", |_: &str| -> Option<u64> { panic!() });
+        assert_eq!(run(&panics, ""), Outcome::Panicked);
+
+        let fails = Implementation::new(1, "fails", |input: &str| input.try_parse::<u64>());
+        assert_eq!(
+            run(&fails, "x"),
+            Outcome::Failed("line 1, column 1: expected u64, got `x`".into())
+        );
+    }
+
+    #[test]
+    fn shrinks_disagreements() {
+        let input = "1\n2\n3\n45\n6\n7\n";
+        let shrunk = shrink(input, |candidate| sum(candidate) != sum_digits(candidate));
+        assert_eq!(shrunk, "45\n");
+    }
+
+    #[test]
+    fn round_trips_args() {
+        let crosscheck = Crosscheck { seeds: 3 };
+        let mut args = vec!["09".to_string()];
+        args.extend(crosscheck.to_args());
+        assert_eq!(Crosscheck::parse_args(&args), Some(crosscheck));
+        assert_eq!(Crosscheck::parse_args(&["09".to_string()]), None);
+    }
+}
@@ -1,3 +1,4 @@
+use std::collections::HashSet;
 use std::error::Error;
 use std::fmt::Display;
 use std::str::FromStr;
@@ -100,6 +101,83 @@ impl Display for DayFromStrError {
 
 /* -------------------------------------------------------------------------- */
 
+/// A set of days, parsed from a comma-separated list of days and ranges, e.g. `1-3,8,24..`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Days(pub HashSet<Day>);
+
+impl Days {
+    /// The days in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = Day> + '_ {
+        all_days().filter(|day| self.0.contains(day))
+    }
+
+    /// The days that are not in `other`, see `--except`.
+    #[must_use]
+    pub fn without(mut self, other: Option<&Days>) -> Self {
+        if let Some(other) = other {
+            self.0.retain(|day| !other.0.contains(day));
+        }
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+}
+
+impl FromIterator<Day> for Days {
+    fn from_iter<T: IntoIterator<Item = Day>>(iter: T) -> Self {
+        Days(iter.into_iter().collect())
+    }
+}
+
+impl FromStr for Days {
+    type Err = String;
+
+    /// Parses e.g. `1-5,8,12..`. A range `a-b` includes both ends, `a..` runs until the 25th.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let day = |s: &str| {
+            Day::from_str(s.trim()).map_err(|e| format!("invalid day `{}`: {e}.", s.trim()))
+        };
+
+        let mut days = HashSet::new();
+
+        for item in s.split(',') {
+            let (first, last) = if let Some(first) = item.strip_suffix("..") {
+                (day(first)?, Day(25))
+            } else if let Some((first, last)) = item.split_once('-') {
+                (day(first)?, day(last)?)
+            } else {
+                let day = day(item)?;
+                (day, day)
+            };
+
+            if first > last {
+                return Err(format!(
+                    "invalid range `{item}`, {first} comes after {last}."
+                ));
+            }
+
+            days.extend((first.0..=last.0).map(Day));
+        }
+
+        Ok(Days(days))
+    }
+}
+
+impl Display for Days {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let days: Vec<String> = self.iter().map(|day| day.to_string()).collect();
+        f.write_str(&days.join(", "))
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
 /// An iterator that yields every day of advent from the 1st to the 25th.
 pub fn all_days() -> AllDays {
     AllDays::new()
@@ -154,7 +232,7 @@ macro_rules! day {
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{all_days, Day};
+    use super::{all_days, Day, Days};
 
     #[test]
     fn all_days_iterator() {
@@ -187,6 +265,50 @@ mod tests {
         assert_eq!(iter.next(), Some(Day(25)));
         assert_eq!(iter.next(), None);
     }
+
+    #[test]
+    fn parses_days_and_ranges() {
+        let parse = |s: &str| s.parse::<Days>().map(|days| days.to_string());
+
+        assert_eq!(parse("7"), Ok("07".into()));
+        assert_eq!(parse("3,1,3"), Ok("01, 03".into()));
+        assert_eq!(parse("2-4"), Ok("02, 03, 04".into()));
+        assert_eq!(parse("9-9"), Ok("09".into()));
+        assert_eq!(parse("23.."), Ok("23, 24, 25".into()));
+        assert_eq!(parse("1-3,8,24.."), Ok("01, 02, 03, 08, 24, 25".into()));
+        assert_eq!(parse(" 1 - 2 , 5 "), Ok("01, 02, 05".into()));
+
+        let invalid = "expecting a day number between 1 and 25";
+        assert_eq!(parse(""), Err(format!("invalid day ``: {invalid}.")));
+        assert_eq!(parse("0"), Err(format!("invalid day `0`: {invalid}.")));
+        assert_eq!(parse("1,x"), Err(format!("invalid day `x`: {invalid}.")));
+        assert_eq!(parse("3-"), Err(format!("invalid day ``: {invalid}.")));
+        assert_eq!(parse("..5"), Err(format!("invalid day `..5`: {invalid}.")));
+        assert_eq!(parse("26.."), Err(format!("invalid day `26`: {invalid}.")));
+        assert_eq!(
+            parse("8-2"),
+            Err("invalid range `8-2`, 08 comes after 02.".into())
+        );
+    }
+
+    #[test]
+    fn parses_day_lists() {
+        let days: Days = "1-5,8,12..,3".parse().unwrap();
+        assert_eq!(days.len(), 5 + 1 + 14);
+        assert_eq!(days.iter().next(), Some(Day(1)));
+
+        let except: Days = "2-24".parse().unwrap();
+        assert_eq!(days.without(Some(&except)).to_string(), "01, 25");
+
+        assert_eq!(
+            "5-1".parse::<Days>(),
+            Err("invalid range `5-1`, 05 comes after 01.".into())
+        );
+        assert_eq!(
+            "1,26".parse::<Days>(),
+            Err("invalid day `26`: expecting a day number between 1 and 25.".into())
+        );
+    }
 }
 
 /* -------------------------------------------------------------------------- */
@@ -0,0 +1,280 @@
+/// Module that describes the machine and toolchain a benchmark was recorded on.
+/// All information is gathered from local sources, nothing is sent anywhere.
+use std::{collections::HashMap, fmt::Display, fs, process::Command, thread};
+use tinyjson::JsonValue;
+
+/// Machine and toolchain context of a benchmark run.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Fingerprint {
+    pub cpu: String,
+    pub cores: usize,
+    /// Operating system and architecture, e.g. `linux x86_64`.
+    pub os: String,
+    /// Output of `rustc --version`.
+    pub rustc: String,
+    /// Cargo profile the solutions were built with.
+    pub profile: String,
+    /// Whether the machine ran on battery power, if it could be determined.
+    pub on_battery: Option<bool>,
+    /// Short hash of the checked out commit, suffixed with `-dirty` for uncommitted changes.
+    pub commit: Option<String>,
+}
+
+impl Fingerprint {
+    /// Collects the fingerprint of the current machine.
+    pub fn detect(is_release: bool) -> Self {
+        Fingerprint {
+            cpu: detect_cpu().unwrap_or_else(|| "unknown".into()),
+            cores: thread::available_parallelism().map_or(1, std::num::NonZero::get),
+            os: format!("{} {}", std::env::consts::OS, std::env::consts::ARCH),
+            rustc: command_output("rustc", &["--version"]).unwrap_or_else(|| "unknown".into()),
+            profile: if is_release { "release" } else { "dev" }.into(),
+            on_battery: detect_battery(),
+            commit: detect_commit(),
+        }
+    }
+
+    /// Whether timings recorded with both fingerprints can be compared with each other.
+    /// The commit is not considered, as comparing code changes is the point of benchmarking.
+    pub fn matches(&self, other: &Self) -> bool {
+        self.cpu == other.cpu
+            && self.cores == other.cores
+            && self.os == other.os
+            && self.rustc == other.rustc
+            && self.profile == other.profile
+            && self.on_battery.unwrap_or(false) == other.on_battery.unwrap_or(false)
+    }
+
+    /// A short identifier of the comparable parts of the fingerprint.
+    pub fn id(&self) -> String {
+        // FNV-1a, which unlike `DefaultHasher` is stable between compiler versions.
+        let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+        let key = format!(
+            "{}|{}|{}|{}|{}|{}",
+            self.cpu,
+            self.cores,
+            self.os,
+            self.rustc,
+            self.profile,
+            self.on_battery.unwrap_or(false)
+        );
+        for byte in key.bytes() {
+            hash ^= u64::from(byte);
+            hash = hash.wrapping_mul(0x0100_0000_01b3);
+        }
+        format!("{:08x}", hash >> 32)
+    }
+}
+
+/// Two optional fingerprints are comparable if both match, or if both are unknown.
+pub fn is_comparable(a: Option<&Fingerprint>, b: Option<&Fingerprint>) -> bool {
+    match (a, b) {
+        (Some(a), Some(b)) => a.matches(b),
+        (None, None) => true,
+        _ => false,
+    }
+}
+
+impl Display for Fingerprint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}, {} cores, {}, {}, {}",
+            self.cpu, self.cores, self.os, self.rustc, self.profile
+        )?;
+        if self.on_battery == Some(true) {
+            write!(f, ", on battery")?;
+        }
+        Ok(())
+    }
+}
+
+fn command_output(cmd: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(cmd).args(args).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if stdout.is_empty() {
+        None
+    } else {
+        Some(stdout)
+    }
+}
+
+fn detect_cpu() -> Option<String> {
+    match std::env::consts::OS {
+        "linux" => fs::read_to_string("/proc/cpuinfo")
+            .ok()?
+            .lines()
+            .find(|l| l.starts_with("model name") || l.starts_with("Model"))
+            .and_then(|l| l.split_once(':'))
+            .map(|(_, v)| v.trim().to_string()),
+        "macos" => command_output("sysctl", &["-n", "machdep.cpu.brand_string"]),
+        "windows" => std::env::var("PROCESSOR_IDENTIFIER").ok(),
+        _ => None,
+    }
+}
+
+fn detect_battery() -> Option<bool> {
+    match std::env::consts::OS {
+        "linux" => {
+            // a mains adapter that is offline means we run on battery.
+            let supplies = fs::read_dir("/sys/class/power_supply").ok()?;
+            let mut mains_online = None;
+            for supply in supplies.flatten() {
+                let path = supply.path();
+                let kind = fs::read_to_string(path.join("type")).unwrap_or_default();
+                if kind.trim() == "Mains" {
+                    let online = fs::read_to_string(path.join("online")).unwrap_or_default();
+                    mains_online = Some(mains_online.unwrap_or(false) || online.trim() == "1");
+                }
+            }
+            mains_online.map(|online| !online)
+        }
+        "macos" => {
+            command_output("pmset", &["-g", "batt"]).map(|out| out.contains("'Battery Power'"))
+        }
+        _ => None,
+    }
+}
+
+fn detect_commit() -> Option<String> {
+    let commit = command_output("git", &["rev-parse", "--short", "HEAD"])?;
+    let dirty = command_output("git", &["status", "--porcelain", "--untracked-files=no"]).is_some();
+    Some(if dirty {
+        format!("{commit}-dirty")
+    } else {
+        commit
+    })
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Fingerprint> for JsonValue {
+    fn from(value: &Fingerprint) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("cpu".into(), JsonValue::String(value.cpu.clone()));
+        #[allow(clippy::cast_precision_loss)]
+        map.insert("cores".into(), JsonValue::Number(value.cores as f64));
+        map.insert("os".into(), JsonValue::String(value.os.clone()));
+        map.insert("rustc".into(), JsonValue::String(value.rustc.clone()));
+        map.insert("profile".into(), JsonValue::String(value.profile.clone()));
+        map.insert(
+            "on_battery".into(),
+            match value.on_battery {
+                Some(x) => JsonValue::Boolean(x),
+                None => JsonValue::Null,
+            },
+        );
+        map.insert(
+            "commit".into(),
+            match &value.commit {
+                Some(x) => JsonValue::String(x.clone()),
+                None => JsonValue::Null,
+            },
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Fingerprint {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected fingerprint to be a JSON object.")?;
+
+        let get_string = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<String>())
+                .cloned()
+                .ok_or(format!("Expected fingerprint.{key} to be a string."))
+        };
+
+        let cores = json
+            .get("cores")
+            .and_then(|v| v.get::<f64>())
+            .filter(|v| v.fract() == 0.0 && **v >= 1.0)
+            .ok_or("Expected fingerprint.cores to be a positive integer.")?;
+
+        Ok(Fingerprint {
+            cpu: get_string("cpu")?,
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            cores: *cores as usize,
+            os: get_string("os")?,
+            rustc: get_string("rustc")?,
+            profile: get_string("profile")?,
+            on_battery: json
+                .get("on_battery")
+                .and_then(|v| v.get::<bool>())
+                .copied(),
+            commit: json.get("commit").and_then(|v| v.get::<String>()).cloned(),
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{is_comparable, Fingerprint};
+    use tinyjson::JsonValue;
+
+    pub fn get_mock_fingerprint() -> Fingerprint {
+        Fingerprint {
+            cpu: "Apple M1".into(),
+            cores: 8,
+            os: "macos aarch64".into(),
+            rustc: "rustc 1.83.0 (90b35a623 2024-11-26)".into(),
+            profile: "release".into(),
+            on_battery: Some(false),
+            commit: Some("abc1234".into()),
+        }
+    }
+
+    #[test]
+    fn ignores_commit_when_matching() {
+        let a = get_mock_fingerprint();
+        let b = Fingerprint {
+            commit: Some("def5678-dirty".into()),
+            ..get_mock_fingerprint()
+        };
+        assert!(a.matches(&b));
+        assert_eq!(a.id(), b.id());
+    }
+
+    #[test]
+    fn detects_different_machines() {
+        let a = get_mock_fingerprint();
+        let b = Fingerprint {
+            on_battery: Some(true),
+            ..get_mock_fingerprint()
+        };
+        let c = Fingerprint {
+            rustc: "rustc 1.84.0".into(),
+            ..get_mock_fingerprint()
+        };
+        assert!(!a.matches(&b));
+        assert!(!a.matches(&c));
+        assert_ne!(a.id(), c.id());
+    }
+
+    #[test]
+    fn compares_unknown_fingerprints() {
+        let a = get_mock_fingerprint();
+        assert!(is_comparable(None, None));
+        assert!(!is_comparable(Some(&a), None));
+        assert!(is_comparable(Some(&a), Some(&a)));
+    }
+
+    #[test]
+    fn round_trips_json() {
+        let a = get_mock_fingerprint();
+        let json = JsonValue::from(&a);
+        assert_eq!(Fingerprint::try_from(&json).unwrap(), a);
+    }
+}
@@ -0,0 +1,213 @@
+/// Module for seeded generators of synthetic puzzle inputs.
+/// A day opts in by passing a [`Generator`] to the `solution!` macro, e.g. `solution!(16, generator = Maze)`.
+use std::{env, fs, io, path::PathBuf, process};
+
+use crate::template::{config, Day};
+
+/// Small, seeded pseudo-random number generator (SplitMix64).
+/// Generated inputs only depend on the seed, so they can be reproduced from it.
+#[derive(Clone, Debug)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Number in `0..n`. `n` must not be zero.
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+
+    /// Number in `min..=max`.
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_possible_wrap,
+        clippy::cast_sign_loss
+    )]
+    pub fn range(&mut self, min: i64, max: i64) -> i64 {
+        let span = max.abs_diff(min) + 1;
+        min + (self.next_u64() % span) as i64
+    }
+
+    /// `true` with a probability of `p`.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn chance(&mut self, p: f64) -> bool {
+        ((self.next_u64() >> 11) as f64 / (1u64 << 53) as f64) < p
+    }
+
+    /// Shuffles `items` in place (Fisher-Yates).
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+}
+
+/// Generator of valid inputs for one day's puzzle.
+pub trait Generator {
+    /// Generates an input from `rng`. `size` scales the input in a day-specific way,
+    /// e.g. the side length of a grid or the number of lines.
+    fn generate(&self, rng: &mut Rng, size: usize) -> String;
+
+    /// Size that approximates a real puzzle input.
+    fn default_size(&self) -> usize;
+}
+
+/// Path of a generated input.
+pub fn get_path(day: Day, seed: u64) -> PathBuf {
+    config::get()
+        .paths
+        .folder("generated")
+        .join(day.to_string())
+        .join(format!("{seed}.txt"))
+}
+
+/// Inputs requested by `cargo generate`, one per seed starting at `seed`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Generation {
+    pub seed: u64,
+    pub count: u64,
+    /// Size passed to the generator, defaults to [`Generator::default_size`].
+    pub size: Option<usize>,
+}
+
+impl Default for Generation {
+    fn default() -> Self {
+        Generation {
+            seed: 1,
+            count: 1,
+            size: None,
+        }
+    }
+}
+
+impl Generation {
+    /// Arguments that forward the generation to a solution binary.
+    pub fn to_args(self) -> Vec<String> {
+        let mut args = vec![
+            "--generate".to_string(),
+            "--seed".into(),
+            self.seed.to_string(),
+            "--count".into(),
+            self.count.to_string(),
+        ];
+        if let Some(size) = self.size {
+            args.push("--size".into());
+            args.push(size.to_string());
+        }
+        args
+    }
+
+    /// Reads the generation forwarded by [`Generation::to_args`] from the command-line.
+    pub fn from_args() -> Option<Self> {
+        let args: Vec<String> = env::args().collect();
+        Self::parse_args(&args)
+    }
+
+    fn parse_args(args: &[String]) -> Option<Self> {
+        if !args.iter().any(|x| x == "--generate") {
+            return None;
+        }
+
+        let value = |name: &str| {
+            args.iter()
+                .position(|x| x == name)
+                .and_then(|i| args.get(i + 1))
+        };
+        let defaults = Generation::default();
+
+        Some(Generation {
+            seed: value("--seed")
+                .and_then(|x| x.parse().ok())
+                .unwrap_or(defaults.seed),
+            count: value("--count")
+                .and_then(|x| x.parse().ok())
+                .unwrap_or(defaults.count),
+            size: value("--size").and_then(|x| x.parse().ok()),
+        })
+    }
+
+    /// Writes the generated inputs to `data/generated/<day>/<seed>.txt`.
+    pub fn write(self, day: Day, generator: &dyn Generator) -> io::Result<()> {
+        let size = self.size.unwrap_or_else(|| generator.default_size());
+
+        for seed in self.seed..self.seed + self.count {
+            let input = generator.generate(&mut Rng::new(seed), size);
+            let path = get_path(day, seed);
+            if let Some(dir) = path.parent() {
+                fs::create_dir_all(dir)?;
+            }
+            fs::write(&path, &input)?;
+            println!("Wrote {} ({} B)", path.display(), input.len());
+        }
+
+        Ok(())
+    }
+}
+
+/// Writes the requested inputs, or exits if the day has no generator or writing failed.
+pub fn write_or_exit(generation: Generation, day: Day, generator: Option<&dyn Generator>) {
+    let Some(generator) = generator else {
+        eprintln!(
+            "Day {day} has no input generator. Register one with `solution!({}, generator = ...)`.",
+            day.into_inner()
+        );
+        process::exit(1);
+    };
+
+    if let Err(e) = generation.write(day, generator) {
+        eprintln!("Failed to write generated inputs: {e}");
+        process::exit(1);
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Generation, Rng};
+
+    #[test]
+    fn is_deterministic_per_seed() {
+        let draw = |seed| {
+            let mut rng = Rng::new(seed);
+            (0..5).map(|_| rng.range(-3, 3)).collect::<Vec<_>>()
+        };
+        assert_eq!(draw(7), draw(7));
+        assert_ne!(draw(7), draw(8));
+        assert!(draw(9).iter().all(|x| (-3..=3).contains(x)));
+
+        let mut items: Vec<usize> = (0..10).collect();
+        Rng::new(1).shuffle(&mut items);
+        items.sort_unstable();
+        assert_eq!(items, (0..10).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn round_trips_args() {
+        let generation = Generation {
+            seed: 5,
+            count: 3,
+            size: Some(41),
+        };
+        let mut args = vec!["16".to_string()];
+        args.extend(generation.to_args());
+        assert_eq!(Generation::parse_args(&args), Some(generation));
+
+        let defaults = Generation::default();
+        let mut args = vec!["16".to_string()];
+        args.extend(defaults.to_args());
+        assert_eq!(Generation::parse_args(&args), Some(defaults));
+        assert_eq!(Generation::parse_args(&["16".to_string()]), None);
+    }
+}
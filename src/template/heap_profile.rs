@@ -0,0 +1,292 @@
+/// Module that summarizes the `dhat-heap.json` profiles written by `cargo solve --dhat`.
+use std::{collections::HashMap, fmt::Display, fs, io, path::Path};
+use tinyjson::JsonValue;
+
+use crate::template::run_multi::get_path_for_bin;
+use crate::template::Day;
+
+/// Number of allocation sites listed in a summary.
+const TOP_SITES: usize = 5;
+
+#[derive(Debug)]
+pub enum Error {
+    IO(io::Error),
+    Parser(String),
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::IO(e) => write!(f, "could not read profile: {e}"),
+            Error::Parser(e) => write!(f, "could not parse profile: {e}"),
+        }
+    }
+}
+
+/// A line of the solution file that allocated, or `None` for allocations outside of it.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Location {
+    pub line: u32,
+    pub function: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Site {
+    pub location: Option<Location>,
+    pub bytes: u64,
+    pub blocks: u64,
+}
+
+/// Heap usage of a profiled run, with its most expensive allocation sites.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Summary {
+    pub day: Day,
+    pub total_bytes: u64,
+    pub total_blocks: u64,
+    /// Bytes and blocks alive when the heap was at its largest.
+    pub peak_bytes: u64,
+    pub peak_blocks: u64,
+    /// Sites ordered by total bytes, limited to [`TOP_SITES`].
+    pub sites: Vec<Site>,
+}
+
+impl Summary {
+    pub fn read_from_file(path: &Path, day: Day) -> Result<Self, Error> {
+        Self::from_json(&fs::read_to_string(path)?, day)
+    }
+
+    pub fn from_json(json: &str, day: Day) -> Result<Self, Error> {
+        let value: JsonValue = json
+            .parse()
+            .map_err(|_| Error::Parser("invalid JSON.".into()))?;
+        let document = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or_else(|| Error::Parser("expected a JSON object.".into()))?;
+
+        let frames: Vec<&str> = document
+            .get("ftbl")
+            .and_then(|v| v.get::<Vec<JsonValue>>())
+            .ok_or_else(|| Error::Parser("expected `ftbl` to be an array.".into()))?
+            .iter()
+            .map(|f| f.get::<String>().map_or("", String::as_str))
+            .collect();
+
+        let program_points = document
+            .get("pps")
+            .and_then(|v| v.get::<Vec<JsonValue>>())
+            .ok_or_else(|| Error::Parser("expected `pps` to be an array.".into()))?;
+
+        let bin_path = get_path_for_bin(day);
+        let bin_path = bin_path.trim_start_matches("./");
+
+        let mut summary = Summary {
+            day,
+            total_bytes: 0,
+            total_blocks: 0,
+            peak_bytes: 0,
+            peak_blocks: 0,
+            sites: vec![],
+        };
+        let mut sites: HashMap<Option<Location>, (u64, u64)> = HashMap::new();
+
+        for pp in program_points {
+            let pp = pp
+                .get::<HashMap<String, JsonValue>>()
+                .ok_or_else(|| Error::Parser("expected program points to be objects.".into()))?;
+            let get = |key: &str| pp.get(key).and_then(json_to_u64).unwrap_or(0);
+
+            summary.total_bytes += get("tb");
+            summary.total_blocks += get("tbk");
+            summary.peak_bytes += get("gb");
+            summary.peak_blocks += get("gbk");
+
+            // the first frame within the solution is the line that caused the allocation.
+            let location = pp
+                .get("fs")
+                .and_then(|v| v.get::<Vec<JsonValue>>())
+                .into_iter()
+                .flatten()
+                .filter_map(json_to_u64)
+                .filter_map(|i| frames.get(usize::try_from(i).ok()?))
+                .find_map(|frame| parse_frame(frame, bin_path));
+
+            let site = sites.entry(location).or_default();
+            site.0 += get("tb");
+            site.1 += get("tbk");
+        }
+
+        summary.sites = sites
+            .into_iter()
+            .map(|(location, (bytes, blocks))| Site {
+                location,
+                bytes,
+                blocks,
+            })
+            .collect();
+        summary
+            .sites
+            .sort_by(|a, b| b.bytes.cmp(&a.bytes).then(a.location.cmp(&b.location)));
+        summary.sites.truncate(TOP_SITES);
+
+        Ok(summary)
+    }
+}
+
+fn json_to_u64(value: &JsonValue) -> Option<u64> {
+    value
+        .get::<f64>()
+        .filter(|v| v.fract() == 0.0 && **v >= 0.0)
+        .map(|v| {
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            let v = *v as u64;
+            v
+        })
+}
+
+/// Parses a dhat frame of the form `0x1234: crate::function (src/bin/01.rs:12:5)`,
+/// if it points into `bin_path`.
+fn parse_frame(frame: &str, bin_path: &str) -> Option<Location> {
+    let (symbol, position) = frame.rsplit_once(" (")?;
+    let mut parts = position.strip_suffix(')')?.rsplitn(3, ':');
+    let _column = parts.next()?;
+    let line = parts.next()?.parse().ok()?;
+    let path = parts.next()?;
+
+    if !path.ends_with(bin_path) {
+        return None;
+    }
+
+    let name = symbol.split_once(": ").map_or(symbol, |(_, name)| name);
+    // drop the crate name, which is the same for every frame of a solution.
+    let function = name.split_once("::").map_or(name, |(_, f)| f);
+
+    Some(Location {
+        line,
+        function: function.to_string(),
+    })
+}
+
+impl Display for Summary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "Heap: {} B in {} blocks, peak {} B in {} blocks",
+            self.total_bytes, self.total_blocks, self.peak_bytes, self.peak_blocks
+        )?;
+
+        if self.sites.is_empty() {
+            return Ok(());
+        }
+
+        let bin_path = get_path_for_bin(self.day);
+        let bin_path = bin_path.trim_start_matches("./");
+
+        write!(f, "{:>12}  {:>8}  Location", "Bytes", "Blocks")?;
+        for site in &self.sites {
+            let location = match &site.location {
+                Some(l) => format!("{bin_path}:{} ({})", l.line, l.function),
+                None => "(outside of the solution)".into(),
+            };
+            write!(
+                f,
+                "\n{:>12}  {:>8}  {location}",
+                format!("{} B", site.bytes),
+                site.blocks
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Error, Location, Summary};
+    use crate::day;
+
+    const PROFILE: &str = r#"{
+        "dhatFileVersion": 2, "mode": "rust-heap", "verb": "Allocated", "bklt": true, "bkacc": false,
+        "tu": "µs", "Mtu": "s", "tuth": 10, "cmd": "target/dhat/01", "pid": 1, "tg": 10, "te": 20,
+        "pps": [
+            { "tb": 200, "tbk": 2, "tl": 5, "mb": 200, "mbk": 2, "gb": 200, "gbk": 2, "eb": 0, "ebk": 0, "fs": [1, 2, 3] },
+            { "tb": 64, "tbk": 1, "tl": 5, "mb": 64, "mbk": 1, "gb": 32, "gbk": 0, "eb": 0, "ebk": 0, "fs": [1, 4] },
+            { "tb": 12, "tbk": 1, "tl": 5, "mb": 12, "mbk": 1, "gb": 0, "gbk": 0, "eb": 0, "ebk": 0, "fs": [5] }
+        ],
+        "ftbl": [
+            "[root]",
+            "0x1000: alloc::raw_vec::finish_grow (alloc/src/raw_vec.rs:521:13)",
+            "0x2000: _01::parse (src/bin/01.rs:12:5)",
+            "0x3000: _01::part_one (src/bin/01.rs:30:17)",
+            "0x4000: _01::part_two::{{closure}} (src/bin/01.rs:41:9)",
+            "0x5000: std::rt::lang_start (std/src/rt.rs:159:17)"
+        ]
+    }"#;
+
+    #[test]
+    fn summarizes_profiles() {
+        let summary = Summary::from_json(PROFILE, day!(1)).unwrap();
+        assert_eq!(summary.total_bytes, 276);
+        assert_eq!(summary.total_blocks, 4);
+        assert_eq!(summary.peak_bytes, 232);
+        assert_eq!(summary.peak_blocks, 2);
+
+        let locations: Vec<Option<Location>> =
+            summary.sites.iter().map(|s| s.location.clone()).collect();
+        assert_eq!(
+            locations,
+            vec![
+                Some(Location {
+                    line: 12,
+                    function: "parse".into()
+                }),
+                Some(Location {
+                    line: 41,
+                    function: "part_two::{{closure}}".into()
+                }),
+                None
+            ]
+        );
+    }
+
+    #[test]
+    fn formats_summaries() {
+        let summary = Summary::from_json(PROFILE, day!(1)).unwrap();
+        let expected = [
+            "Heap: 276 B in 4 blocks, peak 232 B in 2 blocks",
+            "       Bytes    Blocks  Location",
+            "       200 B         2  src/bin/01.rs:12 (parse)",
+            "        64 B         1  src/bin/01.rs:41 (part_two::{{closure}})",
+            "        12 B         1  (outside of the solution)",
+        ]
+        .join("\n");
+        assert_eq!(summary.to_string(), expected);
+    }
+
+    #[test]
+    fn rejects_invalid_profiles() {
+        assert!(Summary::from_json("{}", day!(1)).is_err());
+        assert!(Summary::from_json("nope", day!(1)).is_err());
+    }
+
+    #[test]
+    fn reads_profiles_from_files() {
+        let path = std::env::temp_dir().join(format!("dhat-heap-{}.json", std::process::id()));
+        std::fs::write(&path, PROFILE).unwrap();
+        let summary = Summary::read_from_file(&path, day!(1));
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(summary.unwrap().total_bytes, 276);
+
+        assert!(matches!(
+            Summary::read_from_file(&path, day!(1)),
+            Err(Error::IO(_))
+        ));
+    }
+}
@@ -0,0 +1,204 @@
+/// Module that isolates a benchmarking solution binary from scheduler noise.
+/// Isolation is opt-in and best effort: everything that could not be applied is reported.
+use std::{env, fmt::Display};
+
+/// Isolation requested for a benchmark run.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Isolation {
+    /// CPU to pin the solution to. Defaults to the last CPU the process may run on,
+    /// as the first one tends to handle more interrupts.
+    pub cpu: Option<usize>,
+}
+
+impl Isolation {
+    /// Arguments that forward the isolation to a child invocation.
+    pub fn to_args(self) -> Vec<String> {
+        let mut args = vec!["--isolate".to_string()];
+        if let Some(cpu) = self.cpu {
+            args.push("--cpu".into());
+            args.push(cpu.to_string());
+        }
+        args
+    }
+
+    /// Reads the isolation forwarded by [`Isolation::to_args`] from the command-line.
+    pub fn from_args() -> Option<Self> {
+        let args: Vec<String> = env::args().collect();
+        Self::parse_args(&args)
+    }
+
+    fn parse_args(args: &[String]) -> Option<Self> {
+        if !args.iter().any(|x| x == "--isolate") {
+            return None;
+        }
+
+        let cpu = args
+            .iter()
+            .position(|x| x == "--cpu")
+            .and_then(|i| args.get(i + 1))
+            .and_then(|x| x.parse().ok());
+
+        Some(Isolation { cpu })
+    }
+
+    /// Applies the isolation to the current process.
+    pub fn apply(self) -> Report {
+        let mut report = Report::default();
+        platform::apply(self, &mut report);
+        report
+    }
+}
+
+/// Outcome of applying an isolation.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Report {
+    pub applied: Vec<String>,
+    pub skipped: Vec<String>,
+}
+
+impl Display for Report {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.applied.is_empty() {
+            write!(f, "Isolation: none applied")?;
+        } else {
+            write!(f, "Isolation: {}", self.applied.join(", "))?;
+        }
+        for reason in &self.skipped {
+            write!(f, "\n  ⚠ {reason}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Priority the solution is run with, as a nice value.
+#[cfg(target_os = "linux")]
+const NICE: i32 = -10;
+
+#[cfg(target_os = "linux")]
+mod platform {
+    use super::{Isolation, Report, NICE};
+    use std::{fs, io, mem};
+
+    pub fn apply(isolation: Isolation, report: &mut Report) {
+        let cpu = match pin(isolation.cpu) {
+            Ok(cpu) => {
+                report.applied.push(format!("pinned to CPU {cpu}"));
+                Some(cpu)
+            }
+            Err(e) => {
+                report.skipped.push(format!("could not pin to a CPU: {e}"));
+                None
+            }
+        };
+
+        // SAFETY: `setpriority` only reads its arguments.
+        if unsafe { libc::setpriority(libc::PRIO_PROCESS, 0, NICE) } == 0 {
+            report.applied.push(format!("nice {NICE}"));
+        } else {
+            report.skipped.push(format!(
+                "could not raise priority: {} (requires CAP_SYS_NICE)",
+                io::Error::last_os_error()
+            ));
+        }
+
+        let cpu = cpu.unwrap_or(0);
+        let path = format!("/sys/devices/system/cpu/cpu{cpu}/cpufreq/scaling_governor");
+        match fs::read_to_string(path) {
+            Ok(governor) if governor.trim() == "performance" => {
+                report.applied.push("governor `performance`".into());
+            }
+            Ok(governor) => report.skipped.push(format!(
+                "CPU {cpu} uses the `{}` frequency governor, use `performance` for stable timings",
+                governor.trim()
+            )),
+            Err(_) => report.skipped.push(format!(
+                "could not read the frequency governor of CPU {cpu}"
+            )),
+        }
+    }
+
+    fn pin(cpu: Option<usize>) -> Result<usize, String> {
+        // SAFETY: `cpu_set_t` is a plain bitmask, for which all zeroes is a valid value.
+        let mut allowed: libc::cpu_set_t = unsafe { mem::zeroed() };
+        let size = mem::size_of::<libc::cpu_set_t>();
+
+        // SAFETY: `allowed` is a valid, writable `cpu_set_t` of `size` bytes.
+        if unsafe { libc::sched_getaffinity(0, size, &mut allowed) } != 0 {
+            return Err(io::Error::last_os_error().to_string());
+        }
+
+        let max = libc::CPU_SETSIZE as usize;
+        // SAFETY: all indices are below `CPU_SETSIZE`.
+        let is_allowed = |i: usize| unsafe { libc::CPU_ISSET(i, &allowed) };
+
+        let cpu = match cpu {
+            Some(cpu) if cpu < max && is_allowed(cpu) => cpu,
+            Some(cpu) => return Err(format!("CPU {cpu} is not available to this process")),
+            None => (0..max)
+                .rev()
+                .find(|i| is_allowed(*i))
+                .ok_or("no CPU is available to this process")?,
+        };
+
+        // SAFETY: see above.
+        let mut set: libc::cpu_set_t = unsafe { mem::zeroed() };
+        // SAFETY: `cpu` is below `CPU_SETSIZE`.
+        unsafe { libc::CPU_SET(cpu, &mut set) };
+
+        // SAFETY: `set` is a valid `cpu_set_t` of `size` bytes.
+        if unsafe { libc::sched_setaffinity(0, size, &set) } == 0 {
+            Ok(cpu)
+        } else {
+            Err(io::Error::last_os_error().to_string())
+        }
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+mod platform {
+    use super::{Isolation, Report};
+
+    pub fn apply(_: Isolation, report: &mut Report) {
+        report.skipped.push(format!(
+            "CPU pinning, priority and governor checks are not supported on {}",
+            std::env::consts::OS
+        ));
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Isolation, Report};
+
+    #[test]
+    fn round_trips_args() {
+        let args = |i: Isolation| {
+            let mut args = vec!["01".to_string(), "--time".to_string()];
+            args.extend(i.to_args());
+            args
+        };
+
+        let pinned = Isolation { cpu: Some(3) };
+        assert_eq!(Isolation::parse_args(&args(pinned)), Some(pinned));
+
+        let any = Isolation::default();
+        assert_eq!(Isolation::parse_args(&args(any)), Some(any));
+
+        assert_eq!(Isolation::parse_args(&["--time".to_string()]), None);
+    }
+
+    #[test]
+    fn reports_skipped_isolation() {
+        let report = Report {
+            applied: vec!["pinned to CPU 3".into(), "nice -10".into()],
+            skipped: vec!["could not read the frequency governor of CPU 3".into()],
+        };
+        assert_eq!(
+            report.to_string(),
+            "Isolation: pinned to CPU 3, nice -10\n  ⚠ could not read the frequency governor of CPU 3"
+        );
+        assert_eq!(Report::default().to_string(), "Isolation: none applied");
+    }
+}
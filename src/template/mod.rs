@@ -1,15 +1,35 @@
 use std::{env, fs};
 
+pub mod alloc;
 pub mod aoc_cli;
 pub mod commands;
+pub mod config;
+pub mod counters;
+pub mod crosscheck;
+pub mod generate;
+pub mod parse;
+pub mod registry;
 pub mod runner;
+pub mod scale;
+pub mod session;
+pub mod solution;
+pub mod spans;
 
 pub use day::*;
 
+mod answers;
+mod corpus;
 mod day;
+mod fingerprint;
+#[cfg(any(feature = "dhat-heap", feature = "test_lib"))]
+mod heap_profile;
+mod isolation;
 mod readme_benchmarks;
+mod readme_stars;
+mod report;
 mod run_multi;
 mod timings;
+mod verify;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
@@ -19,18 +39,32 @@ pub const ANSI_RESET: &str = "\x1b[0m";
 #[must_use]
 pub fn read_file(folder: &str, day: Day) -> String {
     let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
+    let filepath = cwd
+        .join(config::get().paths.folder(folder))
+        .join(format!("{day}.txt"));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
+/// Reads the puzzle input of a day, or the input passed with `--input <path>`, see `cargo verify --corpus`.
+#[must_use]
+pub fn read_input(day: Day) -> String {
+    let args: Vec<String> = env::args().collect();
+    match args.iter().position(|x| x == "--input") {
+        Some(i) => {
+            let path = args.get(i + 1).expect("missing path after --input");
+            fs::read_to_string(path).expect("could not open input file")
+        }
+        None => read_file("inputs", day),
+    }
+}
+
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
 #[must_use]
 pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
     let cwd = env::current_dir().unwrap();
     let filepath = cwd
-        .join("data")
-        .join(folder)
+        .join(config::get().paths.folder(folder))
         .join(format!("{day}-{part}.txt"));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
@@ -39,30 +73,207 @@ pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
 /// Creates the constant `DAY` and sets up the input and runner for each part.
 ///
 /// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+/// Alternatively, `generator = <expr>` registers a [`generate::Generator`] for synthetic inputs,
+/// and `alternatives = [2 => part_two_alt]` registers other implementations for `cargo crosscheck`.
+/// The parts return `Option<u64>`, other answers are declared with `answers = (Option<String>, Option<u64>)`.
+///
+/// Instead of the free functions `part_one` and `part_two`, a day can implement the
+/// [`solution::Solution`] trait and pass it with `solution!(solution = Day14)`.
 #[macro_export]
 macro_rules! solution {
-    ($day:expr) => {
-        $crate::solution!(@impl $day, [part_one, 1] [part_two, 2]);
+    (
+        solution = $solution:ident
+        $(, generator = $generator:expr)?
+        $(, alternatives = [$( $alt_part:literal => $alt:expr ),* $(,)?])?
+        $(,)?
+    ) => {
+        /// The current day.
+        const DAY: $crate::template::Day =
+            <$solution as $crate::template::solution::Solution>::DAY;
+
+        fn solution() -> $solution {
+            $solution
+        }
+
+        $crate::solution!(@main [$( $generator )?], [$($( ($alt_part, $alt) )*)?]);
     };
     ($day:expr, 1) => {
-        $crate::solution!(@impl $day, [part_one, 1]);
+        $crate::solution!(@parts $day, [], [], [part_one] Option<u64>, [] Option<u8>);
     };
     ($day:expr, 2) => {
-        $crate::solution!(@impl $day, [part_two, 2]);
+        $crate::solution!(@parts $day, [], [], [] Option<u8>, [part_two] Option<u64>);
+    };
+    (
+        $day:expr
+        $(, answers = ($answer_1:ty, $answer_2:ty))?
+        $(, generator = $generator:expr)?
+        $(, alternatives = [$( $alt_part:literal => $alt:expr ),* $(,)?])?
+        $(,)?
+    ) => {
+        $crate::solution!(
+            @parts $day,
+            [$( $generator )?],
+            [$($( ($alt_part, $alt) )*)?],
+            [part_one] $crate::solution!(@answer [$( $answer_1 )?]),
+            [part_two] $crate::solution!(@answer [$( $answer_2 )?])
+        );
     };
 
-    (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
+    (
+        @parts $day:expr,
+        [$( $generator:expr )?],
+        [$( ($alt_part:literal, $alt:expr) )*],
+        [$( $part_one:ident )?] $answer_1:ty,
+        [$( $part_two:ident )?] $answer_2:ty
+    ) => {
         /// The current day.
         const DAY: $crate::template::Day = $crate::day!($day);
 
+        /// The free functions of the current day as a `Solution`.
+        fn solution() -> $crate::template::solution::Parts<{ $day }, $answer_1, $answer_2> {
+            $crate::template::solution::Parts::<{ $day }>::default()
+                $( .with_part_one($part_one) )?
+                $( .with_part_two($part_two) )?
+        }
+
+        $crate::solution!(@main [$( $generator )?], [$( ($alt_part, $alt) )*]);
+    };
+
+    (@main [$( $generator:expr )?], [$( ($alt_part:literal, $alt:expr) )*]) => {
+        // the registry binary includes the solutions as modules and declares the allocator once.
+        #[cfg(not(feature = "registry"))]
+        $crate::solution!(@alloc);
+
+        /// Adds the current day to the registry binary, see `registry!`.
+        #[allow(dead_code)]
+        pub fn register(registry: &mut $crate::template::registry::Registry) {
+            registry.add(solution());
+        }
+
+        fn main() {
+            let solution = solution();
+            let generator: Option<&dyn $crate::template::generate::Generator> =
+                None $( .or(Some(&$generator)) )?;
+
+            if let Some(generation) = $crate::template::generate::Generation::from_args() {
+                $crate::template::generate::write_or_exit(generation, DAY, generator);
+                return;
+            }
+
+            if let Some(crosscheck) = $crate::template::crosscheck::Crosscheck::from_args() {
+                use $crate::template::registry::Implementation;
+                #[allow(unused_mut)]
+                let mut implementations = $crate::template::solution::implementations(solution);
+                $( implementations.push(Implementation::new($alt_part, stringify!($alt), $alt)); )*
+                crosscheck.run(DAY, &implementations, generator);
+                return;
+            }
+
+            if let Some(scaling) = $crate::template::scale::Scaling::from_args() {
+                $crate::template::scale::run(&solution, &scaling, generator);
+                return;
+            }
+
+            let input = $crate::template::read_input(DAY);
+            $crate::template::runner::run(&solution, &input);
+        }
+    };
+
+    // library modules of the solutions, see `y2024`. They only expose the day and its parts.
+    (
+        @library solution = $solution:ident
+        $(, generator = $generator:expr)?
+        $(, alternatives = [$( $alt_tokens:tt )*])?
+        $(,)?
+    ) => {
+        /// The day of this solution.
+        pub const DAY: $crate::template::Day =
+            <$solution as $crate::template::solution::Solution>::DAY;
+
+        // the generator is only run by the binary, see `cargo generate`.
+        $( const _: &dyn $crate::template::generate::Generator = &$generator; )?
+
+        /// Solves both parts of the day. Errors are located in `input`.
+        pub fn solve(input: &str) -> $crate::template::solution::Outcomes<$solution> {
+            $crate::template::solution::solve(&$solution, input)
+        }
+    };
+    (@library $day:expr, 1) => {
+        $crate::solution!(@library_parts $day, [part_one] Option<u64>, [] Option<u8>);
+    };
+    (@library $day:expr, 2) => {
+        $crate::solution!(@library_parts $day, [] Option<u8>, [part_two] Option<u64>);
+    };
+    (
+        @library $day:expr
+        $(, answers = ($answer_1:ty, $answer_2:ty))?
+        $(, generator = $generator:expr)?
+        $(, alternatives = [$( $alt_tokens:tt )*])?
+        $(,)?
+    ) => {
+        // the generator is only run by the binary, see `cargo generate`.
+        $( const _: &dyn $crate::template::generate::Generator = &$generator; )?
+
+        $crate::solution!(
+            @library_parts $day,
+            [part_one] $crate::solution!(@answer [$( $answer_1 )?]),
+            [part_two] $crate::solution!(@answer [$( $answer_2 )?])
+        );
+    };
+    (
+        @library_parts $day:expr,
+        [$( $part_one:ident )?] $answer_1:ty,
+        [$( $part_two:ident )?] $answer_2:ty
+    ) => {
+        /// The day of this solution.
+        pub const DAY: $crate::template::Day = $crate::day!($day);
+
+        /// Solves both parts of the day. Errors are located in `input`.
+        pub fn solve(
+            input: &str,
+        ) -> $crate::template::solution::Outcomes<
+            $crate::template::solution::Parts<{ $day }, $answer_1, $answer_2>,
+        > {
+            let solution: $crate::template::solution::Parts<{ $day }, $answer_1, $answer_2> =
+                $crate::template::solution::Parts::<{ $day }>::default()
+                    $( .with_part_one($part_one) )?
+                    $( .with_part_two($part_two) )?;
+            $crate::template::solution::solve(&solution, input)
+        }
+    };
+
+    // what the free functions of a day return, `Option<u64>` unless given with `answers = (A, B)`.
+    (@answer []) => { Option<u64> };
+    (@answer [$answer:ty]) => { $answer };
+
+    (@alloc) => {
         #[cfg(feature = "dhat-heap")]
         #[global_allocator]
         static ALLOC: dhat::Alloc = dhat::Alloc;
 
+        #[cfg(all(feature = "allocs", not(feature = "dhat-heap")))]
+        #[global_allocator]
+        static ALLOC: $crate::template::alloc::CountingAlloc = $crate::template::alloc::CountingAlloc;
+    };
+}
+
+/// Creates the `main` of the registry binary, which includes the given solutions as modules
+/// and runs them in one process. Requires the `registry` feature.
+#[macro_export]
+macro_rules! registry {
+    ($( $module:ident => $path:literal ),* $(,)?) => {
+        $(
+            #[allow(dead_code)]
+            #[path = $path]
+            mod $module;
+        )*
+
+        $crate::solution!(@alloc);
+
         fn main() {
-            use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
+            let mut registry = $crate::template::registry::Registry::default();
+            $( $module::register(&mut registry); )*
+            registry.run_from_args();
         }
     };
 }
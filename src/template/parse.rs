@@ -0,0 +1,185 @@
+/// Module for parsing puzzle inputs without panicking.
+/// An [`InputError`] points at the slice of the input it is about, so that the runner can print
+/// the line and column of the error instead of a panic backtrace:
+///
+/// ```text
+/// Part 1: ✖ expected i32, got `x1`
+///   --> line 3, column 3
+///    |
+///  3 | p=x1,4 v=3,-3
+///    |   ^^
+/// ```
+use std::{any, fmt::Display, str::FromStr};
+
+/// Line and column of an error in the input, both starting at 1.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Location {
+    pub line: usize,
+    /// Column of the first character of the offending slice, counted in characters.
+    pub column: usize,
+    /// The offending slice in characters, at least 1 so that it can be underlined.
+    pub width: usize,
+    /// The full line of the input, for the diagnostic.
+    pub text: String,
+}
+
+/// Error in a puzzle input. Errors created with [`InputError::at`] are located in the input
+/// by [`InputError::locate`], which the runner calls with the raw input.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InputError {
+    message: String,
+    /// Address and length of the offending slice, as it can only be located in the input it came from.
+    span: Option<(usize, usize)>,
+    location: Option<Location>,
+}
+
+impl InputError {
+    /// An error about the input as a whole, e.g. a missing section.
+    pub fn new(message: impl Into<String>) -> Self {
+        InputError {
+            message: message.into(),
+            span: None,
+            location: None,
+        }
+    }
+
+    /// An error about `span`, which should be a slice of the input.
+    pub fn at(span: &str, message: impl Into<String>) -> Self {
+        InputError {
+            message: message.into(),
+            span: Some((span.as_ptr() as usize, span.len())),
+            location: None,
+        }
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    pub fn location(&self) -> Option<&Location> {
+        self.location.as_ref()
+    }
+
+    /// Resolves the line and column of the error, if its span is a slice of `input`.
+    #[must_use]
+    pub fn locate(mut self, input: &str) -> Self {
+        let Some((address, len)) = self.span else {
+            return self;
+        };
+
+        let start = input.as_ptr() as usize;
+        if address < start || address + len > start + input.len() {
+            return self;
+        }
+
+        let offset = address - start;
+        let line_start = input[..offset].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = input[offset..]
+            .find('\n')
+            .map_or(input.len(), |i| offset + i);
+        let text = input[line_start..line_end].trim_end_matches('\r');
+
+        self.location = Some(Location {
+            line: input[..line_start].matches('\n').count() + 1,
+            column: input[line_start..offset].chars().count() + 1,
+            width: input[offset..offset + len].chars().count().max(1),
+            text: text.to_string(),
+        });
+        self
+    }
+
+    /// The error with its location and the offending line, underlined. Unlocated errors only print their message.
+    pub fn diagnostic(&self) -> String {
+        let Some(location) = &self.location else {
+            return self.message.clone();
+        };
+
+        let number = location.line.to_string();
+        let gutter = " ".repeat(number.len());
+
+        format!(
+            "{}\n {gutter}--> line {}, column {}\n {gutter} |\n {number} | {}\n {gutter} | {}{}",
+            self.message,
+            location.line,
+            location.column,
+            location.text,
+            " ".repeat(location.column - 1),
+            "^".repeat(location.width),
+        )
+    }
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.location {
+            Some(location) => write!(
+                f,
+                "line {}, column {}: {}",
+                location.line, location.column, self.message
+            ),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+impl std::error::Error for InputError {}
+
+/// Fallible counterparts of `str::parse` and `str::split_once`, failing with an [`InputError`] at the slice.
+pub trait ParseExt<'a> {
+    fn try_parse<T: FromStr>(self) -> Result<T, InputError>;
+
+    fn try_split_once(self, delimiter: &str) -> Result<(&'a str, &'a str), InputError>;
+}
+
+impl<'a> ParseExt<'a> for &'a str {
+    fn try_parse<T: FromStr>(self) -> Result<T, InputError> {
+        self.parse().map_err(|_| {
+            let name = any::type_name::<T>();
+            let name = name.rsplit("::").next().unwrap_or(name);
+            InputError::at(self, format!("expected {name}, got `{self}`"))
+        })
+    }
+
+    fn try_split_once(self, delimiter: &str) -> Result<(&'a str, &'a str), InputError> {
+        self.split_once(delimiter)
+            .ok_or_else(|| InputError::at(self, format!("expected `{delimiter}` in `{self}`")))
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{InputError, ParseExt};
+
+    #[test]
+    fn locates_errors() {
+        let input = "1   2\n3   x4\n";
+        let line = input.lines().nth(1).unwrap();
+        let (_, b) = line.try_split_once("   ").unwrap();
+
+        let error = b.try_parse::<u64>().unwrap_err();
+        assert_eq!(error.to_string(), "expected u64, got `x4`");
+
+        let error = error.locate(input);
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 5: expected u64, got `x4`"
+        );
+        assert_eq!(
+            error.diagnostic(),
+            "expected u64, got `x4`\n  --> line 2, column 5\n   |\n 2 | 3   x4\n   |     ^^"
+        );
+    }
+
+    #[test]
+    fn ignores_foreign_spans() {
+        let other = String::from("x");
+        let error = InputError::at(&other, "foreign").locate("1\n2\n");
+        assert_eq!(error.location(), None);
+        assert_eq!(error.diagnostic(), "foreign");
+
+        let error = "1 2".try_split_once(",").unwrap_err().locate("1 2");
+        assert_eq!(error.to_string(), "line 1, column 1: expected `,` in `1 2`");
+    }
+}
@@ -1,8 +1,8 @@
 /// Module that updates the readme me with timing information.
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
-use std::{fs, io};
+use std::{fs, io, str::FromStr, time::Duration};
 
-use crate::template::timings::Timings;
+use crate::template::timings::{PartTiming, Timing, Timings};
 use crate::template::Day;
 
 static MARKER: &str = "<!--- benchmarking table --->";
@@ -21,8 +21,8 @@ impl From<std::io::Error> for Error {
 }
 
 pub struct TablePosition {
-    pos_start: usize,
-    pos_end: usize,
+    pub pos_start: usize,
+    pub pos_end: usize,
 }
 
 #[must_use]
@@ -30,8 +30,9 @@ pub fn get_path_for_bin(day: Day) -> String {
     format!("./src/bin/{day}.rs")
 }
 
-fn locate_table(readme: &str) -> Result<TablePosition, Error> {
-    let matches: Vec<_> = readme.match_indices(MARKER).collect();
+/// Finds the region of the readme enclosed by (and including) a pair of `marker` comments.
+pub(super) fn locate_table(readme: &str, marker: &str) -> Result<TablePosition, Error> {
+    let matches: Vec<_> = readme.match_indices(marker).collect();
 
     if matches.len() > 2 {
         return Err(Error::Parser(
@@ -52,76 +53,420 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     Ok(TablePosition { pos_start, pos_end })
 }
 
-fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
+/// A column of the benchmark table.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Column {
+    Parse,
+    Part1,
+    Part2,
+    Samples,
+    /// First call of each part, before caches were warm.
+    Cold,
+    Memory,
+    Share,
+}
+
+impl Column {
+    fn title(self) -> &'static str {
+        match self {
+            Column::Parse => "Parse",
+            Column::Part1 => "Part 1",
+            Column::Part2 => "Part 2",
+            Column::Samples => "Samples",
+            Column::Cold => "Cold",
+            Column::Memory => "Peak memory",
+            Column::Share => "Share",
+        }
+    }
+}
+
+impl FromStr for Column {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "parse" => Ok(Column::Parse),
+            "part1" => Ok(Column::Part1),
+            "part2" => Ok(Column::Part2),
+            "samples" => Ok(Column::Samples),
+            "cold" => Ok(Column::Cold),
+            "memory" => Ok(Column::Memory),
+            "share" => Ok(Column::Share),
+            x => Err(format!(
+                "unknown column `{x}`, expected one of: parse, part1, part2, samples, cold, memory, share."
+            )),
+        }
+    }
+}
+
+/// A comma-separated list of columns, e.g. `parse,part1,part2,share`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Columns(pub Vec<Column>);
+
+impl FromStr for Columns {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.split(',')
+            .map(Column::from_str)
+            .collect::<Result<_, _>>()
+            .map(Columns)
+    }
+}
+
+/// Row order of the benchmark table.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SortOrder {
+    #[default]
+    Day,
+    /// Most expensive days first.
+    Cost,
+}
+
+impl FromStr for SortOrder {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "day" => Ok(SortOrder::Day),
+            "cost" => Ok(SortOrder::Cost),
+            x => Err(format!(
+                "unknown sort order `{x}`, expected `day` or `cost`."
+            )),
+        }
+    }
+}
+
+/// Character set used to render the per-day cost bar.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BarStyle {
+    Ascii,
+    Unicode,
+}
+
+impl FromStr for BarStyle {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "ascii" => Ok(BarStyle::Ascii),
+            "unicode" => Ok(BarStyle::Unicode),
+            x => Err(format!(
+                "unknown bar style `{x}`, expected `ascii` or `unicode`."
+            )),
+        }
+    }
+}
+
+/// Controls which data the benchmark table shows and how.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TableOptions {
+    pub columns: Vec<Column>,
+    pub sort: SortOrder,
+    pub bar: Option<BarStyle>,
+    /// Adds a row that sums up every column.
+    pub totals: bool,
+}
+
+impl Default for TableOptions {
+    fn default() -> Self {
+        TableOptions {
+            columns: vec![Column::Part1, Column::Part2],
+            sort: SortOrder::Day,
+            bar: None,
+            totals: false,
+        }
+    }
+}
+
+const BAR_WIDTH: usize = 20;
+
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss,
+    clippy::cast_precision_loss
+)]
+fn render_bar(style: BarStyle, ratio: f64) -> String {
+    let ratio = if ratio.is_finite() {
+        ratio.clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+
+    match style {
+        BarStyle::Ascii => {
+            let filled = (ratio * BAR_WIDTH as f64).round() as usize;
+            format!("{}{}", "#".repeat(filled), ".".repeat(BAR_WIDTH - filled))
+        }
+        BarStyle::Unicode => {
+            const PARTIALS: [&str; 8] = ["", "▏", "▎", "▍", "▌", "▋", "▊", "▉"];
+            let eighths = (ratio * (BAR_WIDTH * 8) as f64).round() as usize;
+            format!("{}{}", "█".repeat(eighths / 8), PARTIALS[eighths % 8])
+        }
+    }
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn format_nanos(nanos: f64) -> String {
+    format!("{:.1?}", Duration::from_nanos(nanos as u64))
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{value:.1} {}", UNITS[unit])
+    }
+}
+
+fn format_samples(timing: &Timing) -> String {
+    // sample counts of `0` are unknown, e.g. for timings migrated from an old schema.
+    let samples = |p: Option<PartTiming>| p.map(|p| p.samples).filter(|x| *x > 0);
+
+    match (samples(timing.part_1), samples(timing.part_2)) {
+        (None, None) => "-".into(),
+        (a, b) => format!(
+            "{} / {}",
+            a.map_or_else(|| "-".into(), |x| x.to_string()),
+            b.map_or_else(|| "-".into(), |x| x.to_string())
+        ),
+    }
+}
+
+fn format_cold(timing: &Timing) -> String {
+    let cold = |p: Option<PartTiming>| p.and_then(|p| p.cold_duration());
+
+    match (cold(timing.part_1), cold(timing.part_2)) {
+        (None, None) => "-".into(),
+        (a, b) => format!(
+            "{} / {}",
+            a.map_or_else(|| "`-`".into(), |x| format!("`{x:.1?}`")),
+            b.map_or_else(|| "`-`".into(), |x| format!("`{x:.1?}`"))
+        ),
+    }
+}
+
+fn format_cell(column: Column, timing: &Timing, total_nanos: f64) -> String {
+    let code = |x: Option<PartTiming>| x.map_or_else(|| "`-`".into(), |x| format!("`{x}`"));
+
+    match column {
+        Column::Parse => code(timing.parse),
+        Column::Part1 => code(timing.part_1),
+        Column::Part2 => code(timing.part_2),
+        Column::Samples => format_samples(timing),
+        Column::Cold => format_cold(timing),
+        Column::Memory => timing
+            .peak_memory
+            .map_or_else(|| "-".into(), |x| format!("`{}`", format_bytes(x))),
+        Column::Share => {
+            if total_nanos > 0.0 {
+                format!("{:.1}%", timing.total_nanos / total_nanos * 100.0)
+            } else {
+                "-".into()
+            }
+        }
+    }
+}
+
+fn format_total_cell(column: Column, timings: &Timings) -> String {
+    let sum = |f: fn(&Timing) -> Option<f64>| {
+        let values: Vec<f64> = timings.data.iter().filter_map(f).collect();
+        if values.is_empty() {
+            "-".into()
+        } else {
+            format!("**`{}`**", format_nanos(values.iter().sum()))
+        }
+    };
+
+    match column {
+        Column::Parse => sum(Timing::parse_nanos),
+        Column::Part1 => sum(Timing::part_1_nanos),
+        Column::Part2 => sum(Timing::part_2_nanos),
+        Column::Samples | Column::Cold => String::new(),
+        Column::Memory => timings
+            .data
+            .iter()
+            .filter_map(|t| t.peak_memory)
+            .max()
+            .map_or_else(|| "-".into(), |x| format!("**`{}`**", format_bytes(x))),
+        Column::Share => "**100%**".into(),
+    }
+}
+
+fn construct_table(
+    prefix: &str,
+    mut timings: Timings,
+    total_millis: f64,
+    options: &TableOptions,
+) -> String {
     let header = format!("{prefix} Benchmarks");
 
+    let mut titles: Vec<&str> = vec!["Day"];
+    titles.extend(options.columns.iter().map(|c| c.title()));
+    if options.bar.is_some() {
+        titles.push("Cost");
+    }
+
     let mut lines: Vec<String> = vec![
         MARKER.into(),
         header,
         String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
+        format!("| {} |", titles.join(" | ")),
+        format!("|{}", " :---: |".repeat(titles.len())),
     ];
 
-    for timing in timings.data {
+    if options.sort == SortOrder::Cost {
+        timings
+            .data
+            .sort_by(|a, b| b.total_nanos.total_cmp(&a.total_nanos));
+    }
+
+    let total_nanos = timings.data.iter().map(|t| t.total_nanos).sum::<f64>();
+    let max_nanos = timings
+        .data
+        .iter()
+        .map(|t| t.total_nanos)
+        .fold(0_f64, f64::max);
+
+    // timings are compared against the machine of the most recent run.
+    let reference = timings
+        .data
+        .iter()
+        .filter(|t| t.fingerprint.is_some())
+        .max_by_key(|t| t.recorded_at)
+        .cloned();
+    let mut has_foreign_rows = false;
+
+    for timing in &timings.data {
         let path = get_path_for_bin(timing.day);
-        lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
+        let marker = match &reference {
+            Some(reference) if !timing.is_comparable(reference) => {
+                has_foreign_rows = true;
+                "\\*"
+            }
+            _ => "",
+        };
+        let mut cells = vec![format!(
+            "[Day {}]({}){marker}",
             timing.day.into_inner(),
-            path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
-        ));
+            path
+        )];
+        cells.extend(
+            options
+                .columns
+                .iter()
+                .map(|c| format_cell(*c, timing, total_nanos)),
+        );
+        if let Some(style) = options.bar {
+            cells.push(format!(
+                "`{}`",
+                render_bar(style, timing.total_nanos / max_nanos)
+            ));
+        }
+        lines.push(format!("| {} |", cells.join(" | ")));
+    }
+
+    if options.totals {
+        let mut cells = vec!["**Total**".to_string()];
+        cells.extend(
+            options
+                .columns
+                .iter()
+                .map(|c| format_total_cell(*c, &timings)),
+        );
+        if options.bar.is_some() {
+            cells.push(String::new());
+        }
+        lines.push(format!("| {} |", cells.join(" | ")));
     }
 
     lines.push(String::new());
     lines.push(format!("**Total: {total_millis:.2}ms**"));
+
+    if let Some(fingerprint) = reference.and_then(|r| r.fingerprint) {
+        lines.push(String::new());
+        lines.push(format!("_Measured on: {fingerprint}._"));
+        if has_foreign_rows {
+            lines
+                .push("_\\* measured on a different machine or toolchain, not comparable._".into());
+        }
+    }
+
     lines.push(MARKER.into());
 
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Timings, total_millis: f64) -> Result<(), Error> {
-    let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
+fn update_content(
+    s: &mut String,
+    timings: Timings,
+    total_millis: f64,
+    options: &TableOptions,
+) -> Result<(), Error> {
+    let positions = locate_table(s, MARKER)?;
+    let table = construct_table("##", timings, total_millis, options);
     s.replace_range(positions.pos_start..positions.pos_end, &table);
     Ok(())
 }
 
-pub fn update(timings: Timings) -> Result<(), Error> {
+pub fn update(timings: Timings, options: &TableOptions) -> Result<(), Error> {
     let path = "README.md";
     let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
     let total_millis = timings.total_millis();
-    update_content(&mut readme, timings, total_millis)?;
+    update_content(&mut readme, timings, total_millis, options)?;
     fs::write(path, &readme)?;
     Ok(())
 }
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{update_content, MARKER};
-    use crate::{day, template::timings::Timing, template::timings::Timings};
+    use super::{update_content, BarStyle, Column, Columns, SortOrder, TableOptions, MARKER};
+    use crate::{
+        day,
+        template::fingerprint::Fingerprint,
+        template::timings::{PartTiming, Timing, Timings},
+    };
+
+    fn part(millis: f64) -> Option<PartTiming> {
+        Some(PartTiming {
+            nanos: millis * 1_000_000_f64,
+            samples: 0,
+            cold_nanos: None,
+            allocations: None,
+        })
+    }
 
     fn get_mock_timings() -> Timings {
         Timings {
             data: vec![
                 Timing {
                     day: day!(1),
-                    part_1: Some("10ms".into()),
-                    part_2: Some("20ms".into()),
+                    part_1: part(10.0),
+                    part_2: part(20.0),
                     total_nanos: 3e+10,
+                    ..Timing::new(day!(1))
                 },
                 Timing {
                     day: day!(2),
-                    part_1: Some("30ms".into()),
-                    part_2: Some("40ms".into()),
+                    part_1: part(30.0),
+                    part_2: part(40.0),
                     total_nanos: 7e+10,
+                    ..Timing::new(day!(2))
                 },
                 Timing {
                     day: day!(4),
-                    part_1: Some("40ms".into()),
-                    part_2: Some("50ms".into()),
+                    part_1: part(40.0),
+                    part_2: part(50.0),
                     total_nanos: 9e+10,
+                    ..Timing::new(day!(4))
                 },
             ],
         }
@@ -131,28 +476,28 @@ mod tests {
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &TableOptions::default()).unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
         let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &TableOptions::default()).unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &TableOptions::default()).unwrap();
         assert_eq!(s.contains("## Benchmarks"), true);
     }
 
     #[test]
     fn updates_existing_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &TableOptions::default()).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &TableOptions::default()).unwrap();
         assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
         assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
     }
@@ -160,7 +505,7 @@ mod tests {
     #[test]
     fn format_benchmarks() {
         let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &TableOptions::default()).unwrap();
         let expected = [
             "foo",
             "bar",
@@ -168,10 +513,10 @@ mod tests {
             "## Benchmarks",
             "",
             "| Day | Part 1 | Part 2 |",
-            "| :---: | :---: | :---:  |",
-            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
-            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
-            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+            "| :---: | :---: | :---: |",
+            "| [Day 1](./src/bin/01.rs) | `10.0ms` | `20.0ms` |",
+            "| [Day 2](./src/bin/02.rs) | `30.0ms` | `40.0ms` |",
+            "| [Day 4](./src/bin/04.rs) | `40.0ms` | `50.0ms` |",
             "",
             "**Total: 190.00ms**",
             "<!--- benchmarking table --->",
@@ -180,4 +525,117 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn parses_columns() {
+        let columns: Columns = "parse,part1,share".parse().unwrap();
+        assert_eq!(columns.0, vec![Column::Parse, Column::Part1, Column::Share]);
+        assert!("part3".parse::<Columns>().is_err());
+    }
+
+    #[test]
+    fn format_extended_benchmarks() {
+        let mut timings = get_mock_timings();
+        timings.data[0].part_1.as_mut().unwrap().samples = 100;
+        timings.data[0].part_2.as_mut().unwrap().samples = 50;
+        timings.data[2].peak_memory = Some(3 * 1024 * 1024);
+
+        let options = TableOptions {
+            columns: vec![
+                Column::Part1,
+                Column::Samples,
+                Column::Memory,
+                Column::Share,
+            ],
+            sort: SortOrder::Cost,
+            bar: Some(BarStyle::Ascii),
+            totals: true,
+        };
+
+        let mut s = format!("{}{}", MARKER, MARKER);
+        update_content(&mut s, timings, 190.0, &options).unwrap();
+        let expected = [
+            "<!--- benchmarking table --->",
+            "## Benchmarks",
+            "",
+            "| Day | Part 1 | Samples | Peak memory | Share | Cost |",
+            "| :---: | :---: | :---: | :---: | :---: | :---: |",
+            "| [Day 4](./src/bin/04.rs) | `40.0ms` | - | `3.0 MiB` | 47.4% | `####################` |",
+            "| [Day 2](./src/bin/02.rs) | `30.0ms` | - | - | 36.8% | `################....` |",
+            "| [Day 1](./src/bin/01.rs) | `10.0ms` | 100 / 50 | - | 15.8% | `#######.............` |",
+            "| **Total** | **`80.0ms`** |  | **`3.0 MiB`** | **100%** |  |",
+            "",
+            "**Total: 190.00ms**",
+            "<!--- benchmarking table --->",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+
+    #[test]
+    fn format_cold_times() {
+        let mut timings = get_mock_timings();
+        timings.data[0].part_1.as_mut().unwrap().cold_nanos = Some(25_000_000.0);
+        timings.data[0].part_2.as_mut().unwrap().cold_nanos = Some(1_500_000.0);
+        timings.data[1].part_1.as_mut().unwrap().cold_nanos = Some(40_000_000.0);
+
+        let options = TableOptions {
+            columns: vec![Column::Part1, Column::Part2, Column::Cold],
+            ..TableOptions::default()
+        };
+
+        let mut s = format!("{}{}", MARKER, MARKER);
+        update_content(&mut s, timings, 190.0, &options).unwrap();
+
+        assert!(s.contains("| Day | Part 1 | Part 2 | Cold |"));
+        assert!(
+            s.contains("| [Day 1](./src/bin/01.rs) | `10.0ms` | `20.0ms` | `25.0ms` / `1.5ms` |")
+        );
+        assert!(s.contains("| [Day 2](./src/bin/02.rs) | `30.0ms` | `40.0ms` | `40.0ms` / `-` |"));
+        assert!(s.contains("| [Day 4](./src/bin/04.rs) | `40.0ms` | `50.0ms` | - |"));
+    }
+
+    #[test]
+    fn marks_timings_from_other_machines() {
+        let fingerprint = Fingerprint {
+            cpu: "Apple M1".into(),
+            cores: 8,
+            os: "macos aarch64".into(),
+            rustc: "rustc 1.83.0".into(),
+            profile: "release".into(),
+            on_battery: None,
+            commit: None,
+        };
+
+        let mut timings = get_mock_timings();
+        timings.data[0].fingerprint = Some(Fingerprint {
+            cores: 4,
+            ..fingerprint.clone()
+        });
+        timings.data[0].recorded_at = Some(1);
+        timings.data[1].fingerprint = Some(fingerprint.clone());
+        timings.data[1].recorded_at = Some(2);
+        timings.data[2].fingerprint = Some(fingerprint);
+        timings.data[2].recorded_at = Some(2);
+
+        let mut s = format!("{}{}", MARKER, MARKER);
+        update_content(&mut s, timings, 190.0, &TableOptions::default()).unwrap();
+
+        assert!(s.contains("| [Day 1](./src/bin/01.rs)\\* |"));
+        assert!(s.contains("| [Day 2](./src/bin/02.rs) |"));
+        assert!(
+            s.contains("_Measured on: Apple M1, 8 cores, macos aarch64, rustc 1.83.0, release._")
+        );
+        assert!(s.contains("_\\* measured on a different machine or toolchain, not comparable._"));
+    }
+
+    #[test]
+    fn renders_unicode_bars() {
+        assert_eq!(super::render_bar(BarStyle::Unicode, 1.0), "█".repeat(20));
+        assert_eq!(super::render_bar(BarStyle::Unicode, 0.0), "");
+        assert_eq!(
+            super::render_bar(BarStyle::Unicode, 0.5 + 1.0 / 320.0),
+            format!("{}▏", "█".repeat(10))
+        );
+    }
 }
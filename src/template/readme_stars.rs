@@ -0,0 +1,172 @@
+/// Module that updates the readme with star progress from the local answer ledger.
+/// Replaces the table that the `advent-readme-stars` action would otherwise render.
+use std::fs;
+
+use crate::template::answers::{Answer, Answers};
+use crate::template::aoc_cli;
+use crate::template::readme_benchmarks::{locate_table, Error};
+
+static MARKER: &str = "<!--- advent_readme_stars table --->";
+
+fn format_star(answer: Option<&Answer>) -> String {
+    match answer {
+        Some(answer) => format!("⭐ `{}`", format_timestamp(answer.completed_at)),
+        None => " ".into(),
+    }
+}
+
+/// Formats a unix timestamp as an UTC date & time, e.g. `2024-12-01 05:12`.
+fn format_timestamp(timestamp: u64) -> String {
+    let days = timestamp / 86_400;
+    let seconds = timestamp % 86_400;
+    let (year, month, day) = civil_from_days(days);
+    format!(
+        "{year:04}-{month:02}-{day:02} {:02}:{:02}",
+        seconds / 3600,
+        (seconds % 3600) / 60
+    )
+}
+
+/// Converts days since the unix epoch to a (year, month, day) triple.
+/// see: http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+fn civil_from_days(days: u64) -> (u64, u64, u64) {
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + u64::from(month <= 2);
+    (year, month, day)
+}
+
+fn construct_table(prefix: &str, answers: &Answers, year: Option<u16>) -> String {
+    let header = match year {
+        Some(year) => format!("{prefix} {year} Results"),
+        None => format!("{prefix} Results"),
+    };
+
+    let mut lines: Vec<String> = vec![
+        MARKER.into(),
+        header,
+        String::new(),
+        "| Day | Title | Part 1 | Part 2 |".into(),
+        "| :---: | :--- | :---: | :---: |".into(),
+    ];
+
+    for entry in answers.data.iter().filter(|a| a.stars() > 0) {
+        let day = entry.day.into_inner();
+        let label = match year {
+            Some(year) => format!("[Day {day}](https://adventofcode.com/{year}/day/{day})"),
+            None => format!("Day {day}"),
+        };
+
+        lines.push(format!(
+            "| {} | {} | {} | {} |",
+            label,
+            entry.title.as_deref().unwrap_or("-"),
+            format_star(entry.part_1.as_ref()),
+            format_star(entry.part_2.as_ref()),
+        ));
+    }
+
+    lines.push(String::new());
+    lines.push(format!("**Stars: {} / 50**", answers.total_stars()));
+    lines.push(MARKER.into());
+
+    lines.join("\n")
+}
+
+fn update_content(s: &mut String, answers: &Answers, year: Option<u16>) -> Result<(), Error> {
+    let positions = locate_table(s, MARKER)?;
+    let table = construct_table("##", answers, year);
+    s.replace_range(positions.pos_start..positions.pos_end, &table);
+    Ok(())
+}
+
+pub fn update(answers: &Answers) -> Result<(), Error> {
+    let path = "README.md";
+    let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
+    update_content(&mut readme, answers, aoc_cli::get_year())?;
+    fs::write(path, &readme)?;
+    Ok(())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_timestamp, update_content, MARKER};
+    use crate::{
+        day,
+        template::answers::{Answer, Answers},
+    };
+
+    fn get_mock_answers() -> Answers {
+        let mut answers = Answers::default();
+        let answer = |completed_at| Answer {
+            value: "42".into(),
+            completed_at,
+        };
+        answers
+            .record(
+                day!(1),
+                1,
+                answer(1_733_029_920),
+                Some("Historian Hysteria".into()),
+            )
+            .unwrap();
+        answers
+            .record(day!(1), 2, answer(1_733_030_520), None)
+            .unwrap();
+        answers
+            .record(day!(3), 1, answer(1_733_203_200), None)
+            .unwrap();
+        answers
+    }
+
+    #[test]
+    fn formats_timestamps() {
+        assert_eq!(format_timestamp(0), "1970-01-01 00:00");
+        assert_eq!(format_timestamp(1_733_029_920), "2024-12-01 05:12");
+        assert_eq!(format_timestamp(1_709_210_096), "2024-02-29 12:34");
+    }
+
+    #[test]
+    #[should_panic]
+    fn errors_if_marker_not_present() {
+        let mut s = "# readme".to_string();
+        update_content(&mut s, &get_mock_answers(), Some(2024)).unwrap();
+    }
+
+    #[test]
+    fn updates_existing_stars() {
+        let mut s = format!("foo\n{}\nbar", MARKER);
+        update_content(&mut s, &get_mock_answers(), Some(2024)).unwrap();
+        update_content(&mut s, &get_mock_answers(), Some(2024)).unwrap();
+        assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
+        assert_eq!(s.matches("## 2024 Results").collect::<Vec<&str>>().len(), 1);
+    }
+
+    #[test]
+    fn format_stars() {
+        let mut s = format!("foo\n{}\nbar", MARKER);
+        update_content(&mut s, &get_mock_answers(), Some(2024)).unwrap();
+        let expected = [
+            "foo",
+            "<!--- advent_readme_stars table --->",
+            "## 2024 Results",
+            "",
+            "| Day | Title | Part 1 | Part 2 |",
+            "| :---: | :--- | :---: | :---: |",
+            "| [Day 1](https://adventofcode.com/2024/day/1) | Historian Hysteria | ⭐ `2024-12-01 05:12` | ⭐ `2024-12-01 05:22` |",
+            "| [Day 3](https://adventofcode.com/2024/day/3) | - | ⭐ `2024-12-03 05:20` |   |",
+            "",
+            "**Stars: 3 / 50**",
+            "<!--- advent_readme_stars table --->",
+            "bar",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+}
@@ -0,0 +1,193 @@
+/// Module for running every day in one binary, as an alternative to one binary per day.
+/// The registry binary (`src/registry.rs`) includes the solutions as modules, see `cargo all --registry`.
+use std::{env, fs, rc::Rc, str::FromStr};
+
+use crate::template::config;
+use crate::template::runner;
+use crate::template::solution::{self, Answer, Outcome, Solution};
+use crate::template::{Day, ANSI_BOLD, ANSI_RESET};
+
+/// Options of the registry binary that take a value.
+const VALUE_OPTIONS: [&str; 2] = ["--part", "--submit"];
+
+/// Implementation type-erased to a function returning a printable answer.
+type Solver = Box<dyn Fn(&str) -> Outcome<String>>;
+
+/// Solution type-erased to a function returning the printable answers of both parts.
+type DaySolver = Box<dyn Fn(&str) -> (Outcome<String>, Outcome<String>)>;
+
+/// One implementation of a part. Answers are compared by their `Display` output.
+pub struct Implementation {
+    pub part: u8,
+    pub name: &'static str,
+    func: Solver,
+}
+
+impl Implementation {
+    pub fn new<A: Answer>(
+        part: u8,
+        name: &'static str,
+        func: impl Fn(&str) -> A + 'static,
+    ) -> Self {
+        Implementation {
+            part,
+            name,
+            func: Box::new(move |input| {
+                func(input).into_outcome().map(|answer| answer.to_string())
+            }),
+        }
+    }
+
+    /// Solves the part, with errors located in `input`.
+    pub fn solve(&self, input: &str) -> Outcome<String> {
+        (self.func)(input).locate(input)
+    }
+}
+
+/// A registered day, type-erased to its runner and solver.
+struct Entry {
+    day: Day,
+    title: Option<&'static str>,
+    /// Runs the day like its own binary, see [`runner::run`].
+    run: Box<dyn Fn(&str)>,
+    /// Solves both parts, parsing the input once.
+    solve: DaySolver,
+}
+
+/// Every registered day, see `solution!` and `registry!`.
+#[derive(Default)]
+pub struct Registry {
+    days: Vec<Entry>,
+}
+
+impl Registry {
+    /// Registers a solution under its day.
+    pub fn add<S: Solution + 'static>(&mut self, solution: S) {
+        let solution = Rc::new(solution);
+        let run = {
+            let solution = Rc::clone(&solution);
+            Box::new(move |input: &str| runner::run(&*solution, input))
+        };
+        let solve: DaySolver = Box::new(move |input: &str| {
+            let (part_one, part_two) = solution::solve(&*solution, input);
+            (
+                part_one.map(|answer| answer.to_string()),
+                part_two.map(|answer| answer.to_string()),
+            )
+        });
+
+        self.days.retain(|e| e.day != S::DAY);
+        self.days.push(Entry {
+            day: S::DAY,
+            title: S::TITLE,
+            run,
+            solve,
+        });
+        self.days.sort_unstable_by_key(|e| e.day);
+    }
+
+    pub fn days(&self) -> impl Iterator<Item = Day> + '_ {
+        self.days.iter().map(|e| e.day)
+    }
+
+    /// Solves one part of a registered day, unsolved if the day or part is not registered.
+    pub fn solve(&self, day: Day, part: u8, input: &str) -> Outcome<String> {
+        let Some(entry) = self.entry(day) else {
+            return Outcome::Unsolved;
+        };
+        let (part_one, part_two) = (entry.solve)(input);
+        match part {
+            1 => part_one,
+            2 => part_two,
+            _ => Outcome::Unsolved,
+        }
+    }
+
+    fn entry(&self, day: Day) -> Option<&Entry> {
+        self.days.iter().find(|e| e.day == day)
+    }
+
+    /// Runs the days passed on the command-line, or every registered day.
+    /// Like the day binaries, it accepts `--part` and `--time`.
+    pub fn run_from_args(&self) {
+        let args: Vec<String> = env::args().skip(1).collect();
+        let mut days: Vec<Day> = args
+            .iter()
+            .enumerate()
+            // the value of `--part 1` is not a day.
+            .filter(|&(i, _)| i == 0 || !VALUE_OPTIONS.contains(&args[i - 1].as_str()))
+            .filter_map(|(_, x)| Day::from_str(x).ok())
+            .collect();
+        days.sort_unstable();
+        days.dedup();
+
+        if days.is_empty() {
+            self.run(&self.days().collect::<Vec<_>>());
+        } else {
+            self.run(&days);
+        }
+    }
+
+    /// Runs `days` one after another, with the same output as `cargo all`.
+    pub fn run(&self, days: &[Day]) {
+        for (i, &day) in days.iter().enumerate() {
+            if i > 0 {
+                println!();
+            }
+
+            match self.entry(day).and_then(|e| e.title) {
+                Some(title) => println!("{ANSI_BOLD}Day {day}: {title}{ANSI_RESET}"),
+                None => println!("{ANSI_BOLD}Day {day}{ANSI_RESET}"),
+            }
+            println!("------");
+
+            let Some(entry) = self.entry(day) else {
+                println!("Not registered.");
+                continue;
+            };
+
+            let path = config::get().paths.inputs.join(format!("{day}.txt"));
+            let Ok(input) = fs::read_to_string(path) else {
+                println!("No input.");
+                continue;
+            };
+
+            (entry.run)(&input);
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Registry;
+    use crate::day;
+    use crate::template::solution::{Outcome, Parts};
+
+    fn count_lines(input: &str) -> Option<usize> {
+        Some(input.lines().count())
+    }
+
+    #[test]
+    fn solves_registered_parts() {
+        let mut registry = Registry::default();
+        registry.add(Parts::<2>::default().with_part_one(count_lines));
+        registry.add(
+            Parts::<1>::default()
+                .with_part_one(|_| Some(1))
+                .with_part_two(|_| None::<u8>),
+        );
+
+        assert_eq!(registry.days().collect::<Vec<_>>(), vec![day!(1), day!(2)]);
+        assert_eq!(
+            registry.solve(day!(2), 1, "a\nb\n"),
+            Outcome::Solved("2".into())
+        );
+        assert_eq!(registry.solve(day!(2), 2, "a\nb\n"), Outcome::Unsolved);
+        assert_eq!(registry.solve(day!(1), 1, ""), Outcome::Solved("1".into()));
+        assert_eq!(registry.solve(day!(1), 2, ""), Outcome::Unsolved);
+        assert_eq!(registry.solve(day!(1), 3, ""), Outcome::Unsolved);
+        assert_eq!(registry.solve(day!(3), 1, ""), Outcome::Unsolved);
+    }
+}
@@ -0,0 +1,494 @@
+/// Module that renders stored timings and answers into standalone reports.
+use std::{fmt::Write, str::FromStr, time::Duration};
+
+use crate::template::answers::{Answers, DayAnswers};
+use crate::template::fingerprint::Fingerprint;
+use crate::template::timings::{Timing, Timings};
+use crate::template::{all_days, Day};
+
+/// Output format of a report.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    Csv,
+    Markdown,
+    Html,
+}
+
+impl Format {
+    pub fn extension(self) -> &'static str {
+        match self {
+            Format::Csv => "csv",
+            Format::Markdown => "md",
+            Format::Html => "html",
+        }
+    }
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "csv" => Ok(Format::Csv),
+            "md" | "markdown" => Ok(Format::Markdown),
+            "html" => Ok(Format::Html),
+            x => Err(format!(
+                "unknown report format `{x}`, expected `csv`, `md` or `html`."
+            )),
+        }
+    }
+}
+
+/// A single report row, joining the timing and the answers of a day.
+struct Row<'a> {
+    day: Day,
+    timing: Option<&'a Timing>,
+    /// Most recent earlier run of the day that was measured on a comparable machine.
+    baseline: Option<&'a Timing>,
+    answers: Option<&'a DayAnswers>,
+}
+
+impl Row<'_> {
+    fn title(&self) -> &str {
+        self.answers
+            .and_then(|a| a.title.as_deref())
+            .unwrap_or_default()
+    }
+
+    fn stars(&self) -> u8 {
+        self.answers.map_or(0, DayAnswers::stars)
+    }
+
+    fn nanos(&self, f: fn(&Timing) -> Option<f64>) -> Option<f64> {
+        self.timing.and_then(f)
+    }
+
+    fn total_nanos(&self) -> Option<f64> {
+        self.timing.map(|t| t.total_nanos)
+    }
+
+    fn machine(&self) -> Option<String> {
+        self.timing
+            .and_then(|t| t.fingerprint.as_ref())
+            .map(Fingerprint::id)
+    }
+
+    /// Relative change of the total runtime against the baseline, in percent.
+    fn change_pct(&self) -> Option<f64> {
+        let current = self.total_nanos()?;
+        let previous = self.baseline?.total_nanos;
+        if previous > 0.0 {
+            Some((current - previous) / previous * 100.0)
+        } else {
+            None
+        }
+    }
+}
+
+fn find_baseline<'a>(timing: &Timing, history: &'a Timings) -> Option<&'a Timing> {
+    let recorded_at = timing.recorded_at?;
+    history
+        .data
+        .iter()
+        .filter(|t| t.day == timing.day && t.is_comparable(timing))
+        .filter(|t| t.recorded_at.is_some_and(|r| r < recorded_at))
+        .max_by_key(|t| t.recorded_at)
+}
+
+fn collect_rows<'a>(
+    timings: &'a Timings,
+    history: &'a Timings,
+    answers: &'a Answers,
+) -> Vec<Row<'a>> {
+    all_days()
+        .map(|day| {
+            let timing = timings.data.iter().find(|t| t.day == day);
+            Row {
+                day,
+                timing,
+                baseline: timing.and_then(|t| find_baseline(t, history)),
+                answers: answers.get(day),
+            }
+        })
+        .filter(|row| row.timing.is_some() || row.answers.is_some())
+        .collect()
+}
+
+/// Renders a report. Runtimes are compared against earlier runs in `history`
+/// only if both were measured on a machine with a matching fingerprint.
+pub fn render(format: Format, timings: &Timings, history: &Timings, answers: &Answers) -> String {
+    let rows = collect_rows(timings, history, answers);
+    match format {
+        Format::Csv => render_csv(&rows),
+        Format::Markdown => render_markdown(&rows, timings),
+        Format::Html => render_html(&rows, timings, answers),
+    }
+}
+
+fn opt_to_string<T: ToString>(x: Option<T>) -> String {
+    x.map(|x| x.to_string()).unwrap_or_default()
+}
+
+fn format_change(pct: Option<f64>) -> String {
+    pct.map_or_else(|| "-".into(), |x| format!("{x:+.1}%"))
+}
+
+/// Distinct machines the timings were recorded on, in order of appearance.
+fn machines<'a>(rows: &[Row<'a>]) -> Vec<&'a Fingerprint> {
+    let mut result: Vec<&Fingerprint> = vec![];
+    for fingerprint in rows.iter().filter_map(|r| r.timing?.fingerprint.as_ref()) {
+        if !result.iter().any(|f| f.matches(fingerprint)) {
+            result.push(fingerprint);
+        }
+    }
+    result
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn format_nanos(nanos: f64) -> String {
+    format!("{:.1?}", Duration::from_nanos(nanos as u64))
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn escape_csv(s: &str) -> String {
+    if s.contains([',', '"', '\n']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+fn render_csv(rows: &[Row]) -> String {
+    let mut out = String::from(
+        "day,title,stars,parse_nanos,part_1_nanos,part_2_nanos,part_1_samples,part_2_samples,part_1_cold_nanos,part_2_cold_nanos,peak_memory_bytes,total_nanos,machine,change_pct\n",
+    );
+
+    for row in rows {
+        let fields = [
+            row.day.to_string(),
+            escape_csv(row.title()),
+            row.stars().to_string(),
+            opt_to_string(row.nanos(Timing::parse_nanos)),
+            opt_to_string(row.nanos(Timing::part_1_nanos)),
+            opt_to_string(row.nanos(Timing::part_2_nanos)),
+            opt_to_string(row.timing.and_then(|t| t.part_1).map(|p| p.samples)),
+            opt_to_string(row.timing.and_then(|t| t.part_2).map(|p| p.samples)),
+            opt_to_string(row.timing.and_then(|t| t.part_1?.cold_nanos)),
+            opt_to_string(row.timing.and_then(|t| t.part_2?.cold_nanos)),
+            opt_to_string(row.timing.and_then(|t| t.peak_memory)),
+            opt_to_string(row.total_nanos()),
+            row.machine().unwrap_or_default(),
+            opt_to_string(row.change_pct().map(|x| format!("{x:.2}"))),
+        ];
+        out.push_str(&fields.join(","));
+        out.push('\n');
+    }
+
+    out
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn render_markdown(rows: &[Row], timings: &Timings) -> String {
+    let code = |x: Option<f64>| x.map_or_else(|| "-".into(), |x| format!("`{}`", format_nanos(x)));
+
+    let mut lines: Vec<String> = vec![
+        "# Advent of Code Report".into(),
+        String::new(),
+        "| Day | Title | Stars | Part 1 | Part 2 | Total | Change |".into(),
+        "| :---: | :--- | :---: | :---: | :---: | :---: | :---: |".into(),
+    ];
+
+    for row in rows {
+        lines.push(format!(
+            "| {} | {} | {} | {} | {} | {} | {} |",
+            row.day.into_inner(),
+            if row.title().is_empty() {
+                "-"
+            } else {
+                row.title()
+            },
+            "⭐".repeat(row.stars().into()),
+            code(row.nanos(Timing::part_1_nanos)),
+            code(row.nanos(Timing::part_2_nanos)),
+            code(row.total_nanos()),
+            format_change(row.change_pct()),
+        ));
+    }
+
+    lines.push(String::new());
+    lines.push(format!("**Total: {:.2}ms**", timings.total_millis()));
+    lines.push(String::new());
+
+    let machines = machines(rows);
+    if !machines.is_empty() {
+        lines.push("## Machines".into());
+        lines.push(String::new());
+        for fingerprint in machines {
+            lines.push(format!("- `{}`: {fingerprint}", fingerprint.id()));
+        }
+        lines.push(String::new());
+    }
+
+    lines.join("\n")
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+const HTML_STYLE: &str = "body{font-family:system-ui,sans-serif;margin:2rem auto;max-width:60rem;color:#222}\
+table{border-collapse:collapse;width:100%}th,td{padding:.3rem .6rem;border-bottom:1px solid #ddd;text-align:right}\
+th{cursor:pointer;user-select:none;background:#f4f4f4}td:nth-child(2),th:nth-child(2){text-align:left}\
+.p1{fill:#4e79a7}.p2{fill:#f28e2b}svg text{font-size:11px;fill:#444}";
+
+const HTML_SCRIPT: &str = "document.querySelectorAll('th').forEach((th,i)=>th.addEventListener('click',()=>{\
+const tbody=th.closest('table').tBodies[0];const asc=th.dataset.asc!=='true';th.dataset.asc=asc;\
+const key=r=>{const c=r.cells[i];const v=c.dataset.sort??c.textContent;const n=parseFloat(v);return isNaN(n)?v:n};\
+[...tbody.rows].sort((a,b)=>{const x=key(a),y=key(b);return (x>y?1:x<y?-1:0)*(asc?1:-1)}).forEach(r=>tbody.appendChild(r))}));";
+
+fn render_chart(rows: &[Row]) -> String {
+    const BAR_HEIGHT: usize = 16;
+    const LABEL_WIDTH: f64 = 60.0;
+    const CHART_WIDTH: f64 = 600.0;
+
+    let timed: Vec<&Row> = rows.iter().filter(|r| r.timing.is_some()).collect();
+    let max = timed
+        .iter()
+        .filter_map(|r| r.total_nanos())
+        .fold(0_f64, f64::max);
+
+    let height = timed.len() * (BAR_HEIGHT + 4);
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{height}\" role=\"img\">",
+        LABEL_WIDTH + CHART_WIDTH + 80.0
+    );
+
+    for (i, row) in timed.iter().enumerate() {
+        let y = i * (BAR_HEIGHT + 4);
+        let scale = |nanos: Option<f64>| {
+            if max > 0.0 {
+                nanos.unwrap_or_default() / max * CHART_WIDTH
+            } else {
+                0.0
+            }
+        };
+        let w1 = scale(row.nanos(Timing::part_1_nanos));
+        let w2 = scale(row.nanos(Timing::part_2_nanos));
+
+        let _ = write!(
+            svg,
+            "<text x=\"0\" y=\"{}\">Day {}</text>\
+             <rect class=\"p1\" x=\"{LABEL_WIDTH}\" y=\"{y}\" width=\"{w1:.1}\" height=\"{BAR_HEIGHT}\"/>\
+             <rect class=\"p2\" x=\"{:.1}\" y=\"{y}\" width=\"{w2:.1}\" height=\"{BAR_HEIGHT}\"/>\
+             <text x=\"{:.1}\" y=\"{}\">{}</text>",
+            y + BAR_HEIGHT - 4,
+            row.day,
+            LABEL_WIDTH + w1,
+            LABEL_WIDTH + w1 + w2 + 4.0,
+            y + BAR_HEIGHT - 4,
+            format_nanos(row.total_nanos().unwrap_or_default()),
+        );
+    }
+
+    svg.push_str("</svg>");
+    svg
+}
+
+fn render_html(rows: &[Row], timings: &Timings, answers: &Answers) -> String {
+    let cell = |x: Option<f64>| match x {
+        Some(x) => format!("<td data-sort=\"{x}\">{}</td>", format_nanos(x)),
+        None => "<td data-sort=\"-1\">-</td>".into(),
+    };
+
+    let mut body = String::new();
+    for row in rows {
+        let _ = write!(
+            body,
+            "<tr><td>{}</td><td>{}</td><td data-sort=\"{}\">{}</td>{}{}{}{}<td data-sort=\"{}\">{}</td><td>{}</td></tr>",
+            row.day,
+            escape_html(row.title()),
+            row.stars(),
+            "⭐".repeat(row.stars().into()),
+            cell(row.nanos(Timing::parse_nanos)),
+            cell(row.nanos(Timing::part_1_nanos)),
+            cell(row.nanos(Timing::part_2_nanos)),
+            cell(row.total_nanos()),
+            row.change_pct().unwrap_or_default(),
+            format_change(row.change_pct()),
+            row.machine().unwrap_or_else(|| "-".into()),
+        );
+    }
+
+    let mut machine_list = String::new();
+    for fingerprint in machines(rows) {
+        let _ = write!(
+            machine_list,
+            "<li><code>{}</code>: {}</li>",
+            fingerprint.id(),
+            escape_html(&fingerprint.to_string())
+        );
+    }
+
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <title>Advent of Code Report</title>\n<style>{HTML_STYLE}</style>\n</head>\n<body>\n\
+         <h1>Advent of Code Report</h1>\n\
+         <p>Stars: {} / 50 &middot; Total runtime: {:.2}ms</p>\n\
+         <h2>Runtime per day</h2>\n\
+         <p><svg width=\"10\" height=\"10\"><rect class=\"p1\" width=\"10\" height=\"10\"/></svg> Part 1 \
+         <svg width=\"10\" height=\"10\"><rect class=\"p2\" width=\"10\" height=\"10\"/></svg> Part 2</p>\n\
+         {}\n\
+         <h2>Details</h2>\n\
+         <table>\n<thead><tr><th>Day</th><th>Title</th><th>Stars</th><th>Parse</th><th>Part 1</th><th>Part 2</th><th>Total</th><th>Change</th><th>Machine</th></tr></thead>\n\
+         <tbody>{body}</tbody>\n</table>\n\
+         <h2>Machines</h2>\n<ul>{machine_list}</ul>\n\
+         <script>{HTML_SCRIPT}</script>\n</body>\n</html>\n",
+        answers.total_stars(),
+        timings.total_millis(),
+        render_chart(rows),
+    )
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{render, Format};
+    use crate::{
+        day,
+        template::{
+            answers::{Answer, Answers},
+            fingerprint::Fingerprint,
+            timings::{PartTiming, Timing, Timings},
+        },
+    };
+
+    fn get_mock_fingerprint(cores: usize) -> Fingerprint {
+        Fingerprint {
+            cpu: "Apple M1".into(),
+            cores,
+            os: "macos aarch64".into(),
+            rustc: "rustc 1.83.0".into(),
+            profile: "release".into(),
+            on_battery: None,
+            commit: None,
+        }
+    }
+
+    fn get_mock_history() -> Timings {
+        let run = |day, total_nanos, recorded_at, cores| Timing {
+            total_nanos,
+            recorded_at: Some(recorded_at),
+            fingerprint: Some(get_mock_fingerprint(cores)),
+            ..Timing::new(day)
+        };
+        Timings {
+            data: vec![
+                run(day!(1), 4e+7, 1, 8),
+                run(day!(1), 1e+7, 2, 4),
+                run(day!(2), 5e+2, 1, 4),
+            ],
+        }
+    }
+
+    fn get_mock_data() -> (Timings, Answers) {
+        let timings = Timings {
+            data: vec![
+                Timing {
+                    day: day!(1),
+                    part_1: Some(PartTiming {
+                        nanos: 1e+7,
+                        samples: 100,
+                        cold_nanos: None,
+                        allocations: None,
+                    }),
+                    part_2: Some(PartTiming {
+                        nanos: 2e+7,
+                        samples: 10,
+                        cold_nanos: None,
+                        allocations: None,
+                    }),
+                    total_nanos: 3e+7,
+                    recorded_at: Some(3),
+                    fingerprint: Some(get_mock_fingerprint(8)),
+                    ..Timing::new(day!(1))
+                },
+                Timing {
+                    day: day!(2),
+                    part_1: Some(PartTiming {
+                        nanos: 1e+3,
+                        samples: 10000,
+                        cold_nanos: None,
+                        allocations: None,
+                    }),
+                    part_2: None,
+                    total_nanos: 1e+3,
+                    ..Timing::new(day!(2))
+                },
+            ],
+        };
+
+        let mut answers = Answers::default();
+        answers
+            .record(
+                day!(1),
+                1,
+                Answer {
+                    value: "42".into(),
+                    completed_at: 0,
+                },
+                Some("Historian, \"Hysteria\" <3".into()),
+            )
+            .unwrap();
+
+        (timings, answers)
+    }
+
+    #[test]
+    fn renders_csv() {
+        let (timings, answers) = get_mock_data();
+        let csv = render(Format::Csv, &timings, &get_mock_history(), &answers);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(
+            lines[1],
+            format!(
+                "01,\"Historian, \"\"Hysteria\"\" <3\",1,,10000000,20000000,100,10,,,,30000000,{},-25.00",
+                get_mock_fingerprint(8).id()
+            )
+        );
+        assert_eq!(lines[2], "02,,0,,1000,,10000,,,,,1000,,");
+    }
+
+    #[test]
+    fn renders_markdown() {
+        let (timings, answers) = get_mock_data();
+        let md = render(Format::Markdown, &timings, &get_mock_history(), &answers);
+        assert!(md.contains(
+            "| 1 | Historian, \"Hysteria\" <3 | ⭐ | `10.0ms` | `20.0ms` | `30.0ms` | -25.0% |"
+        ));
+        assert!(md.contains("| 2 | - |  | `1.0µs` | - | `1.0µs` | - |"));
+        assert!(md.contains("**Total: 30.00ms**"));
+        assert!(md.contains(&format!(
+            "- `{}`: Apple M1, 8 cores, macos aarch64, rustc 1.83.0, release",
+            get_mock_fingerprint(8).id()
+        )));
+    }
+
+    #[test]
+    fn renders_html() {
+        let (timings, answers) = get_mock_data();
+        let html = render(Format::Html, &timings, &Timings::default(), &answers);
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("Historian, &quot;Hysteria&quot; &lt;3"));
+        assert_eq!(html.matches("<rect class=\"p1\" x=").count(), 2);
+        assert!(html.contains("<script>"));
+    }
+}
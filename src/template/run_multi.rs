@@ -1,13 +1,32 @@
-use std::{collections::HashSet, io};
+use std::{collections::HashSet, ffi::OsStr, io, process::Command};
 
 use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
 
 use super::{
     all_days,
+    isolation::Isolation,
     timings::{Timing, Timings},
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
+/// Measurements collected in addition to the runtime of timed runs.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Instrumentation {
+    /// Isolation applied to the solution binaries, see `cargo time --isolate`.
+    pub isolation: Option<Isolation>,
+    /// Count heap allocations of each part.
+    pub allocations: bool,
+    /// Build with the `counters` feature and record the counters of each part.
+    pub counters: bool,
+}
+
+/// Runs the solutions of `days_to_run`, only `part` of each if given.
+pub fn run_multi(
+    days_to_run: &HashSet<Day>,
+    is_release: bool,
+    is_timed: bool,
+    instrumentation: Instrumentation,
+    part: Option<u8>,
+) -> Option<Timings> {
     let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
 
     let mut need_space = false;
@@ -24,7 +43,9 @@ pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -
             println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
             println!("------");
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+            let output =
+                child_commands::run_solution(day, is_timed, is_release, instrumentation, part)
+                    .unwrap();
 
             if output.is_empty() {
                 println!("Not solved.");
@@ -64,11 +85,37 @@ pub fn get_path_for_bin(day: Day) -> String {
     format!("./src/bin/{day}.rs")
 }
 
+/// Command that runs the release build of the solution bin for `day`, passing `args` to the solution.
+#[must_use]
+pub fn run_bin<I, S>(day: Day, args: I) -> Command
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<OsStr>,
+{
+    let mut cmd = Command::new("cargo");
+    cmd.args([
+        "run",
+        "--quiet",
+        "--release",
+        "--bin",
+        &day.to_string(),
+        "--",
+    ])
+    .args(args);
+    cmd
+}
+
 /// All solutions live in isolated binaries.
 /// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
 pub mod child_commands {
-    use super::{get_path_for_bin, Error};
-    use crate::template::Day;
+    use super::{get_path_for_bin, Error, Instrumentation};
+    use crate::template::{
+        alloc::Allocations,
+        counters::Counts,
+        isolation::Isolation,
+        timings::{parse_duration, unix_now, Counter, PartTiming},
+        Day,
+    };
     use std::{
         io::{BufRead, BufReader},
         path::Path,
@@ -77,7 +124,13 @@ pub mod child_commands {
     };
 
     /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
+    pub fn run_solution(
+        day: Day,
+        is_timed: bool,
+        is_release: bool,
+        instrumentation: Instrumentation,
+        part: Option<u8>,
+    ) -> Result<Vec<String>, Error> {
         // skip command invocation for days that have not been scaffolded yet.
         if !Path::new(&get_path_for_bin(day)).exists() {
             return Ok(vec![]);
@@ -90,10 +143,34 @@ pub mod child_commands {
             args.push("--release");
         }
 
+        if instrumentation.counters {
+            args.push("--features");
+            args.push("counters");
+        }
+
+        if instrumentation.allocations {
+            args.push("--features");
+            args.push("allocs");
+        }
+
+        let isolation_args = instrumentation
+            .isolation
+            .map(Isolation::to_args)
+            .unwrap_or_default();
+
+        let part = part.map(|p| p.to_string());
+
+        args.push("--");
+
         if is_timed {
-            // mirror `--time` flag to child invocations.
-            args.push("--");
+            // mirror `--time` and isolation flags to child invocations.
             args.push("--time");
+            args.extend(isolation_args.iter().map(String::as_str));
+        }
+
+        if let Some(part) = &part {
+            args.push("--part");
+            args.push(part);
         }
 
         // spawn child command with piped stdout/stderr.
@@ -129,64 +206,76 @@ pub mod child_commands {
     }
 
     pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
-        let mut timings = super::Timing {
-            day,
-            part_1: None,
-            part_2: None,
-            total_nanos: 0_f64,
-        };
-
-        output
-            .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
+        let mut timings = super::Timing::new(day);
+        timings.recorded_at = Some(unix_now());
+
+        // counters are printed on the line after the part they belong to.
+        let mut last_part = None;
+
+        for l in output {
+            if let Some(counts) = Counts::parse(l) {
+                if let Some(part) = last_part {
+                    timings
+                        .counters
+                        .extend(counts.0.into_iter().map(|(name, value)| Counter {
+                            part,
+                            name,
+                            value,
+                        }));
                 }
+                continue;
+            }
 
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
-                };
-
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
-            })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
-                }
+            if !l.contains(" samples)") {
+                continue;
+            }
 
-                timings.total_nanos += nanos;
-            });
+            let Some(part_timing) = parse_time(l) else {
+                eprintln!("Could not parse timings from line: {l}");
+                continue;
+            };
+
+            if let Some(allocations) = part_timing.allocations {
+                timings.peak_memory = timings.peak_memory.max(Some(allocations.peak));
+            }
+
+            let part = l.split(':').next().unwrap_or_default();
+            last_part = None;
+            if part.contains("Parse") {
+                timings.parse = Some(part_timing);
+            } else if part.contains("Part 1") {
+                timings.part_1 = Some(part_timing);
+                last_part = Some(1);
+            } else if part.contains("Part 2") {
+                timings.part_2 = Some(part_timing);
+                last_part = Some(2);
+            }
+
+            timings.total_nanos += part_timing.nanos;
+        }
 
         timings
     }
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
+    fn parse_time(line: &str) -> Option<PartTiming> {
+        let (measurement, rest) = line.rsplit_once(" samples)")?;
+        let (str_timing, str_samples) = measurement.split('(').next_back()?.split_once('@')?;
+
+        Some(PartTiming {
+            nanos: parse_duration(str_timing)?,
+            samples: str_samples.trim().parse().ok()?,
+            cold_nanos: rest
+                .split(", ")
+                .find_map(|s| s.strip_prefix("cold "))
+                .and_then(parse_duration),
+            allocations: parse_allocations(rest),
+        })
     }
 
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .last()?
-            .split('@')
-            .next()?
-            .trim();
-
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
+    fn parse_allocations(s: &str) -> Option<Allocations> {
+        let end = s.find(" allocs / ")?;
+        let start = s[..end].rfind(", ").map_or(0, |i| i + 2);
+        Allocations::parse(&s[start..])
     }
 
     /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
@@ -220,8 +309,86 @@ pub mod child_commands {
                 day!(1),
             );
             assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
+            assert_approx_eq!(res.part_1.unwrap().nanos, 74.13_f64);
+            assert_approx_eq!(res.part_2.unwrap().nanos, 74130000_f64);
+            assert_eq!(res.part_1.unwrap().samples, 100000);
+            assert_eq!(res.part_2.unwrap().samples, 99999);
+            assert_eq!(res.part_1.unwrap().cold_nanos, None);
+        }
+
+        #[test]
+        fn parses_cold_times() {
+            let res = parse_exec_time(
+                &[
+                    "Part 1: 0 (74.13ns @ 100000 samples) ±1.5%, cold 12.5µs".into(),
+                    "Part 2: 10 (2ms @ 10 samples), cold 3ms".into(),
+                    "".into(),
+                ],
+                day!(1),
+            );
+            assert_approx_eq!(res.total_nanos, 2000074.13_f64);
+            assert_approx_eq!(res.part_1.unwrap().cold_nanos.unwrap(), 12500_f64);
+            assert_approx_eq!(res.part_2.unwrap().cold_nanos.unwrap(), 3000000_f64);
+        }
+
+        #[test]
+        fn parses_allocations() {
+            let res = parse_exec_time(
+                &[
+                    "Part 1: 0 (74.13ns @ 100000 samples) ±1.5%, cold 12.5µs, 3 allocs / 4096 B, peak 2048 B".into(),
+                    "Part 2: 10 (2ms @ 10 samples), cold 3ms, 1 allocs / 64 B, peak 8192 B".into(),
+                    "".into(),
+                ],
+                day!(1),
+            );
+            let allocations = res.part_1.unwrap().allocations.unwrap();
+            assert_eq!(allocations.count, 3);
+            assert_eq!(allocations.bytes, 4096);
+            assert_eq!(allocations.peak, 2048);
+            assert_approx_eq!(res.part_1.unwrap().cold_nanos.unwrap(), 12500_f64);
+            assert_eq!(res.peak_memory, Some(8192));
+        }
+
+        #[test]
+        fn parses_counters() {
+            let res = parse_exec_time(
+                &[
+                    "Part 1: 7036 (1ms @ 10 samples)".into(),
+                    "  counters: heap_pops=120, pushes=98".into(),
+                    "Part 2: 45 (2ms @ 10 samples)".into(),
+                    "  counters: heap_pops=300".into(),
+                    "".into(),
+                ],
+                day!(16),
+            );
+            let counters: Vec<(u8, &str, u64)> = res
+                .counters
+                .iter()
+                .map(|c| (c.part, c.name.as_str(), c.value))
+                .collect();
+            assert_eq!(
+                counters,
+                vec![
+                    (1, "heap_pops", 120),
+                    (1, "pushes", 98),
+                    (2, "heap_pops", 300)
+                ]
+            );
+        }
+
+        #[test]
+        fn parses_parse_times() {
+            let res = parse_exec_time(
+                &[
+                    "Parse: (1.5ms @ 10 samples)".into(),
+                    "Part 1: 0 (2ms @ 10 samples)".into(),
+                    "".into(),
+                ],
+                day!(1),
+            );
+            assert_approx_eq!(res.total_nanos, 3500000_f64);
+            assert_approx_eq!(res.parse.unwrap().nanos, 1500000_f64);
+            assert_approx_eq!(res.part_1.unwrap().nanos, 2000000_f64);
         }
 
         #[test]
@@ -235,8 +402,8 @@ pub mod child_commands {
                 day!(1),
             );
             assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
+            assert_approx_eq!(res.part_1.unwrap().nanos, 2000000000_f64);
+            assert_approx_eq!(res.part_2.unwrap().nanos, 100000000_f64);
         }
 
         #[test]
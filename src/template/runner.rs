@@ -2,99 +2,402 @@
 use std::fmt::Display;
 use std::hint::black_box;
 use std::io::{stdout, Write};
+use std::panic::{self, AssertUnwindSafe};
 use std::process::Output;
+use std::sync::Once;
 use std::time::{Duration, Instant};
 use std::{cmp, env, process};
 
+use crate::template::alloc::{self, Allocations};
+use crate::template::answers::{read_puzzle_title, Answer, Answers};
+use crate::template::counters::{self, Counts};
+use crate::template::isolation::Isolation;
+use crate::template::parse::InputError;
+use crate::template::readme_stars;
+use crate::template::solution::{self, Answer as _, Outcome, Solution};
+#[cfg(feature = "spans")]
+use crate::template::spans;
+use crate::template::timings::unix_now;
 use crate::template::ANSI_BOLD;
-use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{aoc_cli, config, Day, ANSI_ITALIC, ANSI_RESET};
 
-pub fn run_part<I: Copy, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+/// Runs the implemented parts of a solution on `input`, after timing its parse step.
+/// Only the part passed with `--part` runs, if any.
+/// Errors are printed with their location in `input`, see [`InputError::diagnostic`].
+pub fn run<S: Solution>(solution: &S, input: &str) {
+    let selected = selected_part();
+    if let Some(part) = selected.filter(|&part| !solution.has_part(part)) {
+        eprintln!("Part {part} of day {} is not implemented.", S::DAY);
+        return;
+    }
+    let is_selected = |part: u8| solution.has_part(part) && selected.is_none_or(|p| p == part);
+
+    let parsed = if S::TIME_PARSE {
+        run_parse(|input| solution.parse(input), input)
+    } else {
+        solution.parse(input)
+    };
+
+    let parsed = match parsed {
+        Ok(parsed) => parsed,
+        Err(error) => {
+            if !S::TIME_PARSE {
+                println!("Parse: ✖ {}", error.message());
+            }
+            print_location(&error.locate(input));
+            return;
+        }
+    };
+
+    if is_selected(1) {
+        let part_one = |parsed| solution.part_one(parsed).into_outcome().locate(input);
+        run_part(part_one, &parsed, S::DAY, 1);
+    }
+
+    if is_selected(2) {
+        let part_two = |parsed| solution.part_two(parsed).into_outcome().locate(input);
+        run_part(part_two, &parsed, S::DAY, 2);
+    }
+}
+
+/// Parses the value of `--part`, see `cargo solve NN --part 2`.
+pub fn parse_part(s: &str) -> Result<u8, String> {
+    match s {
+        "1" => Ok(1),
+        "2" => Ok(2),
+        _ => Err(format!("`{s}` is not a part, expected `1` or `2`.")),
+    }
+}
+
+/// The part passed with `--part`, `None` to run both parts.
+fn selected_part() -> Option<u8> {
+    let args: Vec<String> = env::args().collect();
+    let i = args.iter().position(|x| x == "--part")?;
+
+    match args.get(i + 1).map(|part| parse_part(part)) {
+        Some(Ok(part)) => Some(part),
+        Some(Err(e)) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+        None => {
+            eprintln!("Missing part after `--part`.");
+            process::exit(1);
+        }
+    }
+}
+
+/// Runs the parse step of a solution. It is timed like a part, but has no result to print.
+fn run_parse<'a, T>(
+    func: impl Fn(&'a str) -> Result<T, InputError>,
+    input: &'a str,
+) -> Result<T, InputError> {
+    isolate();
+
+    let (parsed, measurement) = run_timed(func, input, |_| print!("Parse:"));
+
+    print!("\r");
+    match &parsed {
+        Ok(_) => println!("Parse:{measurement}"),
+        Err(error) => println!("Parse: ✖ {}{measurement}", error.message()),
+    }
+
+    if !measurement.counters.is_empty() {
+        println!("{}", measurement.counters);
+    }
+
+    if let Some(spans) = &measurement.spans {
+        println!("{spans}");
+    }
+
+    parsed
+}
+
+/// Runs one part and prints its answer. Parts that return an [`InputError`] print it below the part,
+/// located in the input if the part passes it to [`Outcome::locate`].
+pub fn run_part<I: Copy, A: solution::Answer>(func: impl Fn(I) -> A, input: I, day: Day, part: u8) {
     let part_str = format!("Part {part}");
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    isolate();
+
+    let (result, measurement) = run_timed(
+        |input| func(input).into_outcome(),
+        input,
+        |result| print_result(result, &part_str, ""),
+    );
+
+    print_result(&result, &part_str, &measurement.to_string());
+
+    if let Outcome::Failed(error) = &result {
+        print_location(error);
+    }
+
+    if !measurement.counters.is_empty() {
+        println!("{}", measurement.counters);
+    }
+
+    if let Some(spans) = &measurement.spans {
+        println!("{spans}");
+    }
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    #[cfg(feature = "dhat-heap")]
+    print_heap_profile(day);
 
-    if let Some(result) = result {
+    if let Outcome::Solved(result) = result {
         submit_result(result, day, part);
     }
 }
 
+/// Prints where in the input an error occurred, below the line that printed its message.
+fn print_location(error: &InputError) {
+    if let Some((_, location)) = error.diagnostic().split_once('\n') {
+        println!("{location}");
+    }
+}
+
+/// Summarize the heap profile that dhat wrote after the first call of the part.
+#[cfg(feature = "dhat-heap")]
+fn print_heap_profile(day: Day) {
+    use crate::template::heap_profile::Summary;
+
+    match Summary::read_from_file(std::path::Path::new("dhat-heap.json"), day) {
+        Ok(summary) => println!("{summary}"),
+        Err(e) => eprintln!("Failed to summarize heap profile: {e}"),
+    }
+}
+
+/// Apply the isolation requested by `cargo time --isolate`, once per solution binary.
+fn isolate() {
+    static ISOLATED: Once = Once::new();
+
+    ISOLATED.call_once(|| {
+        if !env::args().any(|x| x == "--time") {
+            return;
+        }
+        if let Some(isolation) = Isolation::from_args() {
+            println!("{}", isolation.apply());
+        }
+    });
+}
+
+/// Per-call timing of a solution part.
+struct Measurement {
+    mean: Duration,
+    samples: u128,
+    /// Relative standard deviation of the per-call time between batches, in percent.
+    deviation: Option<f64>,
+    /// Duration of the first call, if the part was benched afterwards without instrumentation.
+    cold: Option<Duration>,
+    /// Heap usage of the first call, if allocations were counted.
+    allocations: Option<Allocations>,
+    /// Rendered breakdown of the spans entered by the first call, see `aoc_span!`.
+    spans: Option<String>,
+    /// Counters bumped by the first call, see `aoc_count!`.
+    counters: Counts,
+}
+
+impl Display for Measurement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.samples == 1 {
+            write!(f, " ({:.1?})", self.mean)?;
+        } else {
+            write!(f, " ({:.1?} @ {} samples)", self.mean, self.samples)?;
+            if let Some(deviation) = self.deviation {
+                write!(f, " ±{deviation:.1}%")?;
+            }
+            if let Some(cold) = self.cold {
+                write!(f, ", cold {cold:.1?}")?;
+            }
+        }
+        if let Some(allocations) = self.allocations {
+            write!(f, ", {allocations}")?;
+        }
+        Ok(())
+    }
+}
+
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
 ///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
-fn run_timed<I: Copy, T>(
-    func: impl Fn(I) -> T,
-    input: I,
-    hook: impl Fn(&T),
-) -> (T, Duration, u128) {
-    let timer = Instant::now();
-    let result = {
+///     The first, cold call is reported next to the warm average, as it includes one-time costs like lazy initialization.
+fn run_timed<I: Copy, T>(func: impl Fn(I) -> T, input: I, hook: impl Fn(&T)) -> (T, Measurement) {
+    let first_call = || {
         #[cfg(feature = "dhat-heap")]
         let _profiler = dhat::Profiler::new_heap();
 
-        func(input)
+        let timer = Instant::now();
+        #[cfg(feature = "spans")]
+        let ((result, counts), recorder) = spans::record(|| counters::record(|| func(input)));
+        #[cfg(not(feature = "spans"))]
+        let (result, counts) = counters::record(|| func(input));
+        let elapsed = timer.elapsed();
+
+        #[cfg(feature = "spans")]
+        let spans = Some(recorder.render(elapsed)).filter(|s| !s.is_empty());
+        #[cfg(not(feature = "spans"))]
+        let spans = None;
+
+        (result, elapsed, spans, counts)
     };
-    let base_time = timer.elapsed();
+
+    // the counting allocator is replaced by dhat's when profiling with dhat.
+    let ((result, base_time, spans, counters), allocations) =
+        if cfg!(all(feature = "allocs", not(feature = "dhat-heap"))) {
+            let (call, allocations) = alloc::measure(first_call);
+            (call, Some(allocations))
+        } else {
+            (first_call(), None)
+        };
 
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
+    // instrumentation slows down the first call, which would make the cold time incomparable.
+    let instrumented = allocations.is_some()
+        || cfg!(any(
+            feature = "dhat-heap",
+            feature = "spans",
+            feature = "counters"
+        ));
+
+    let measurement = if std::env::args().any(|x| x == "--time") {
+        Measurement {
+            cold: (!instrumented).then_some(base_time),
+            allocations,
+            spans,
+            counters,
+            ..bench(func, input, &base_time)
+        }
     } else {
-        (base_time, 1)
+        Measurement {
+            mean: base_time,
+            samples: 1,
+            deviation: None,
+            cold: None,
+            allocations,
+            spans,
+            counters,
+        }
     };
 
-    (result, run.0, run.1)
+    (result, measurement)
+}
+
+/// Runs `f` and returns `None` if it panicked, without printing the panic message.
+pub(crate) fn catch_silently<T>(f: impl FnOnce() -> T) -> Option<T> {
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    panic::set_hook(hook);
+    result.ok()
+}
+
+/// Minimum duration of a timed batch, long enough for the timer overhead to be negligible.
+const BATCH_WINDOW: Duration = Duration::from_micros(100);
+
+/// Time `batch_size` consecutive calls of `func`.
+fn time_batch<I: Copy, T>(func: &impl Fn(I) -> T, input: I, batch_size: u128) -> Duration {
+    let timer = Instant::now();
+    for _ in 0..batch_size {
+        black_box(func(black_box(input)));
+    }
+    timer.elapsed()
+}
+
+/// Find how many calls fit into one timing window, starting from the duration of the first call.
+/// Returns the batch size and the duration of the last calibration batch.
+fn calibrate_batch_size<I: Copy, T>(
+    func: &impl Fn(I) -> T,
+    input: I,
+    base_time: &Duration,
+) -> (u128, Duration) {
+    let mut batch_size = 1;
+    let mut elapsed = *base_time;
+
+    while elapsed < BATCH_WINDOW {
+        // grow towards the window, but at most tenfold to not overshoot on noisy first calls.
+        let factor = (BATCH_WINDOW.as_nanos() / cmp::max(elapsed.as_nanos(), 1)).clamp(2, 10);
+        batch_size *= factor;
+        elapsed = time_batch(func, input, batch_size);
+    }
+
+    (batch_size, elapsed)
 }
 
-fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
+fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> Measurement {
     let mut stdout = stdout();
 
     print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
     let _ = stdout.flush();
 
-    let bench_iterations =
-        (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
+    bench_for(&func, input, base_time, config::get().bench.budget)
+}
+
+/// Average duration of a call, benched for approx. `budget` after a first call that took `base_time`.
+pub(crate) fn mean_call_time<I: Copy, T>(
+    func: &impl Fn(I) -> T,
+    input: I,
+    base_time: &Duration,
+    budget: Duration,
+) -> Duration {
+    bench_for(func, input, base_time, budget).mean
+}
 
-    let mut timers: Vec<Duration> = vec![];
+fn bench_for<I: Copy, T>(
+    func: &impl Fn(I) -> T,
+    input: I,
+    base_time: &Duration,
+    budget: Duration,
+) -> Measurement {
+    let (batch_size, batch_time) = calibrate_batch_size(func, input, base_time);
+    let bench = config::get().bench;
+    let batch_count = (budget.as_nanos() / cmp::max(batch_time.as_nanos(), 10))
+        .clamp(bench.min_batches, bench.max_batches);
 
-    for _ in 0..bench_iterations {
-        let timer = Instant::now();
-        black_box(func(black_box(input)));
-        timers.push(timer.elapsed());
-    }
+    let batches: Vec<Duration> = (0..batch_count)
+        .map(|_| time_batch(func, input, batch_size))
+        .collect();
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
+    summarize(&batches, batch_size)
 }
 
-fn average_duration(numbers: &[Duration]) -> u128 {
-    numbers
+/// Derive per-call statistics from the durations of equally sized batches.
+#[allow(
+    clippy::cast_precision_loss,
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss
+)]
+fn summarize(batches: &[Duration], batch_size: u128) -> Measurement {
+    let per_call: Vec<f64> = batches
         .iter()
-        .map(std::time::Duration::as_nanos)
-        .sum::<u128>()
-        / numbers.len() as u128
-}
+        .map(|d| d.as_nanos() as f64 / batch_size as f64)
+        .collect();
+
+    let n = per_call.len() as f64;
+    let mean = per_call.iter().sum::<f64>() / n;
 
-fn format_duration(duration: &Duration, samples: u128) -> String {
-    if samples == 1 {
-        format!(" ({duration:.1?})")
+    let deviation = if per_call.len() > 1 && mean > 0.0 {
+        let variance = per_call.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.0);
+        Some(variance.sqrt() / mean * 100.0)
     } else {
-        format!(" ({duration:.1?} @ {samples} samples)")
+        None
+    };
+
+    Measurement {
+        mean: Duration::from_nanos(mean.round() as u64),
+        samples: batches.len() as u128 * batch_size,
+        deviation,
+        cold: None,
+        allocations: None,
+        spans: None,
+        counters: Counts::default(),
     }
 }
 
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
+fn print_result<T: Display>(result: &Outcome<T>, part: &str, duration_str: &str) {
     let is_intermediate_result = duration_str.is_empty();
 
     match result {
-        Some(result) => {
+        Outcome::Solved(result) => {
             if result.to_string().contains('\n') {
                 let str = format!("{part}: ▼ {duration_str}");
                 if is_intermediate_result {
@@ -114,7 +417,7 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
                 }
             }
         }
-        None => {
+        Outcome::Unsolved => {
             if is_intermediate_result {
                 print!("{part}: ✖");
             } else {
@@ -122,6 +425,15 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
                 println!("{part}: ✖             ");
             }
         }
+        Outcome::Failed(error) => {
+            let str = format!("{part}: ✖ {}", error.message());
+            if is_intermediate_result {
+                print!("{str}");
+            } else {
+                print!("\r");
+                println!("{str}{duration_str}");
+            }
+        }
     }
 }
 
@@ -161,5 +473,97 @@ fn submit_result<T: Display>(
     }
 
     println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    let output = aoc_cli::submit(day, part, &result.to_string());
+
+    if let Ok(output) = &output {
+        if aoc_cli::is_correct_answer(output) {
+            record_star(day, part, result.to_string());
+        }
+    }
+
+    Some(output)
+}
+
+/// Add an accepted answer to the ledger and refresh the readme star table.
+fn record_star(day: Day, part: u8, value: String) {
+    let mut answers = Answers::read_from_file();
+    let answer = Answer {
+        value,
+        completed_at: unix_now(),
+    };
+    if let Err(e) = answers.record(day, part, answer, read_puzzle_title(day)) {
+        eprintln!("Failed to record the answer: {e}");
+        return;
+    }
+
+    if answers.store_file().is_err() || readme_stars::update(&answers).is_err() {
+        eprintln!("Failed to store updated star progress.");
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::summarize;
+    use std::time::Duration;
+
+    #[test]
+    fn derives_per_call_statistics_from_batches() {
+        let batches = [
+            Duration::from_nanos(900),
+            Duration::from_nanos(1000),
+            Duration::from_nanos(1100),
+        ];
+        let measurement = summarize(&batches, 10);
+
+        assert_eq!(measurement.mean, Duration::from_nanos(100));
+        assert_eq!(measurement.samples, 30);
+        assert!((measurement.deviation.unwrap() - 10.0).abs() < 1e-9);
+        assert_eq!(measurement.to_string(), " (100.0ns @ 30 samples) ±10.0%");
+    }
+
+    #[test]
+    fn formats_cold_runs() {
+        let measurement = super::Measurement {
+            cold: Some(Duration::from_micros(12)),
+            ..summarize(&[Duration::from_nanos(1000); 2], 10)
+        };
+        assert_eq!(
+            measurement.to_string(),
+            " (100.0ns @ 20 samples) ±0.0%, cold 12.0µs"
+        );
+    }
+
+    #[test]
+    fn formats_allocations() {
+        let measurement = super::Measurement {
+            allocations: Some(super::Allocations {
+                count: 12,
+                bytes: 3456,
+                peak: 2048,
+            }),
+            ..summarize(&[Duration::from_micros(5)], 1)
+        };
+        assert_eq!(
+            measurement.to_string(),
+            " (5.0µs), 12 allocs / 3456 B, peak 2048 B"
+        );
+    }
+
+    #[test]
+    fn parses_parts() {
+        assert_eq!(super::parse_part("2"), Ok(2));
+        assert_eq!(
+            super::parse_part("3"),
+            Err("`3` is not a part, expected `1` or `2`.".into())
+        );
+    }
+
+    #[test]
+    fn formats_single_runs() {
+        let measurement = summarize(&[Duration::from_micros(5)], 1);
+        assert_eq!(measurement.deviation, None);
+        assert_eq!(measurement.to_string(), " (5.0µs)");
+    }
 }
@@ -0,0 +1,407 @@
+/// Module that estimates how the runtime of a solution part grows with the size of its input.
+/// The input is truncated to increasing sizes, and a power law `t = c · nᵏ` is fitted to the timings.
+use std::{
+    env,
+    fmt::Display,
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+use crate::template::generate::{Generator, Rng};
+use crate::template::runner::{catch_silently, mean_call_time};
+use crate::template::solution::{parse_and_solve, Answer, Solution};
+use crate::template::{read_file, Day, ANSI_BOLD, ANSI_RESET};
+
+/// Time spent benching each size.
+const BUDGET_PER_SIZE: Duration = Duration::from_millis(200);
+
+/// Number of sizes a part is run at if not specified.
+pub const DEFAULT_STEPS: usize = 6;
+
+/// Seed of the inputs generated for scaling.
+const SEED: u64 = 1;
+
+/// How inputs of smaller sizes are created.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Strategy {
+    /// Keep a prefix of the lines of every section.
+    #[default]
+    Lines,
+    /// Keep the top-left corner of every section, treating it as a grid.
+    Grid,
+    /// Generate inputs of increasing size with the day's [`Generator`].
+    Generator,
+}
+
+impl FromStr for Strategy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "lines" => Ok(Strategy::Lines),
+            "grid" => Ok(Strategy::Grid),
+            "generator" => Ok(Strategy::Generator),
+            _ => Err(format!(
+                "unknown scaling strategy `{s}`, expected `lines`, `grid` or `generator`."
+            )),
+        }
+    }
+}
+
+impl Display for Strategy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Strategy::Lines => write!(f, "lines"),
+            Strategy::Grid => write!(f, "grid"),
+            Strategy::Generator => write!(f, "generator"),
+        }
+    }
+}
+
+impl Strategy {
+    /// Reduces `input` to roughly `fraction` of its size.
+    /// Sections separated by blank lines are reduced independently, so that every section stays present.
+    /// Only `grid` crops columns, all other strategies keep a prefix of the lines.
+    pub fn truncate(self, input: &str, fraction: f64) -> String {
+        input
+            .trim_end()
+            .split("\n\n")
+            .map(|section| {
+                let lines: Vec<&str> = section.lines().collect();
+                match self {
+                    Strategy::Lines | Strategy::Generator => {
+                        lines[..keep(lines.len(), fraction)].join("\n")
+                    }
+                    Strategy::Grid => {
+                        // keep the same share of rows and columns, so that the area shrinks by `fraction`.
+                        let side = fraction.sqrt();
+                        lines[..keep(lines.len(), side)]
+                            .iter()
+                            .map(|line| {
+                                let end = line
+                                    .char_indices()
+                                    .nth(keep(line.chars().count(), side))
+                                    .map_or(line.len(), |(i, _)| i);
+                                &line[..end]
+                            })
+                            .collect::<Vec<_>>()
+                            .join("\n")
+                    }
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("\n\n")
+            + "\n"
+    }
+}
+
+/// Number of items to keep out of `len`, at least one.
+#[allow(
+    clippy::cast_precision_loss,
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss
+)]
+fn keep(len: usize, fraction: f64) -> usize {
+    ((len as f64 * fraction).ceil() as usize).clamp(1.min(len), len)
+}
+
+/// Scaling run requested by `cargo scale`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Scaling {
+    pub strategy: Strategy,
+    /// Number of sizes to run at, halving the input from one half down.
+    pub steps: usize,
+}
+
+impl Default for Scaling {
+    fn default() -> Self {
+        Scaling {
+            strategy: Strategy::default(),
+            steps: DEFAULT_STEPS,
+        }
+    }
+}
+
+impl Scaling {
+    /// Arguments that forward the scaling to a solution binary.
+    pub fn to_args(self) -> Vec<String> {
+        vec![
+            "--scale".into(),
+            self.strategy.to_string(),
+            "--steps".into(),
+            self.steps.to_string(),
+        ]
+    }
+
+    /// Reads the scaling forwarded by [`Scaling::to_args`] from the command-line.
+    pub fn from_args() -> Option<Self> {
+        let args: Vec<String> = env::args().collect();
+        Self::parse_args(&args)
+    }
+
+    fn parse_args(args: &[String]) -> Option<Self> {
+        let value = |name: &str| {
+            args.iter()
+                .position(|x| x == name)
+                .and_then(|i| args.get(i + 1))
+        };
+
+        let strategy = value("--scale")?.parse().ok()?;
+        let steps = value("--steps")
+            .and_then(|x| x.parse().ok())
+            .unwrap_or(DEFAULT_STEPS);
+
+        Some(Scaling { strategy, steps })
+    }
+
+    /// Input fractions the part is run at, from smallest to largest.
+    #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+    fn fractions(self) -> Vec<f64> {
+        (1..=self.steps)
+            .rev()
+            .map(|i| 0.5_f64.powi(i as i32))
+            .collect()
+    }
+
+    /// Inputs for every step, and the size in bytes of the full input that timings are projected to.
+    fn inputs(
+        self,
+        day: Day,
+        generator: Option<&dyn Generator>,
+    ) -> Result<(Vec<String>, usize), String> {
+        if self.strategy == Strategy::Generator {
+            let generator = generator.ok_or_else(|| {
+                format!("day {day} has no input generator, see `cargo generate`.")
+            })?;
+            let full = generator.default_size();
+            let generate = |size| generator.generate(&mut Rng::new(SEED), size);
+
+            return Ok((
+                self.fractions()
+                    .into_iter()
+                    .map(|f| generate(keep(full, f)))
+                    .collect(),
+                generate(full).len(),
+            ));
+        }
+
+        let input = read_file("inputs", day);
+        Ok((
+            self.fractions()
+                .into_iter()
+                .map(|f| self.strategy.truncate(&input, f))
+                .collect(),
+            input.len(),
+        ))
+    }
+}
+
+/// Power law `t = c · nᵏ`, fitted by least squares in log-log space.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Fit {
+    /// The exponent `k`.
+    pub exponent: f64,
+    /// `ln(c)`, with `t` in nanoseconds and `n` in bytes.
+    pub intercept: f64,
+    /// Coefficient of determination of the fit in log-log space.
+    pub r_squared: f64,
+}
+
+impl Fit {
+    /// Fits `(size, nanos)` points. Requires at least two distinct sizes.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn from_points(points: &[(f64, f64)]) -> Option<Self> {
+        let logs: Vec<(f64, f64)> = points
+            .iter()
+            .filter(|(n, t)| *n > 0.0 && *t > 0.0)
+            .map(|(n, t)| (n.ln(), t.ln()))
+            .collect();
+
+        let count = logs.len() as f64;
+        let mean_x = logs.iter().map(|p| p.0).sum::<f64>() / count;
+        let mean_y = logs.iter().map(|p| p.1).sum::<f64>() / count;
+
+        let sxx: f64 = logs.iter().map(|(x, _)| (x - mean_x).powi(2)).sum();
+        let sxy: f64 = logs.iter().map(|(x, y)| (x - mean_x) * (y - mean_y)).sum();
+        let syy: f64 = logs.iter().map(|(_, y)| (y - mean_y).powi(2)).sum();
+
+        if logs.len() < 2 || sxx == 0.0 {
+            return None;
+        }
+
+        let exponent = sxy / sxx;
+        Some(Fit {
+            exponent,
+            intercept: mean_y - exponent * mean_x,
+            r_squared: if syy == 0.0 {
+                1.0
+            } else {
+                sxy * sxy / (sxx * syy)
+            },
+        })
+    }
+
+    /// Projected duration at an input of `size` bytes.
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub fn project(&self, size: f64) -> Duration {
+        let nanos = (self.intercept + self.exponent * size.ln()).exp();
+        // `as` saturates for durations beyond `u64::MAX` nanoseconds.
+        Duration::from_nanos(nanos.round() as u64)
+    }
+
+    /// Name of the closest common complexity class.
+    pub fn class(&self) -> &'static str {
+        match self.exponent {
+            k if k < 0.5 => "constant",
+            k if k < 1.25 => "linear",
+            k if k < 1.75 => "between linear and quadratic",
+            k if k < 2.5 => "quadratic",
+            k if k < 3.5 => "cubic",
+            _ => "worse than cubic",
+        }
+    }
+}
+
+impl Display for Fit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "O(n^{:.2}), {} (r² {:.3})",
+            self.exponent,
+            self.class(),
+            self.r_squared
+        )
+    }
+}
+
+/// Estimates how the implemented parts of a solution scale, parsing each truncated input.
+pub fn run<S: Solution>(solution: &S, scaling: &Scaling, generator: Option<&dyn Generator>) {
+    if solution.has_part(1) {
+        let part_one =
+            |input: &str| parse_and_solve(solution, input, |parsed| solution.part_one(parsed));
+        run_part(part_one, S::DAY, 1, scaling, generator);
+    }
+
+    if solution.has_part(2) {
+        let part_two =
+            |input: &str| parse_and_solve(solution, input, |parsed| solution.part_two(parsed));
+        run_part(part_two, S::DAY, 2, scaling, generator);
+    }
+}
+
+/// Runs a part at increasing input sizes and prints the fitted growth curve.
+/// Sizes at which the part fails, e.g. because truncation cut off a required part of the input, are skipped.
+#[allow(clippy::cast_precision_loss)]
+pub fn run_part<A: Answer>(
+    func: impl Fn(&str) -> A,
+    day: Day,
+    part: u8,
+    scaling: &Scaling,
+    generator: Option<&dyn Generator>,
+) {
+    println!(
+        "{ANSI_BOLD}Part {part}{ANSI_RESET} (by {}, {} steps)",
+        scaling.strategy, scaling.steps
+    );
+
+    let (inputs, full_size) = match scaling.inputs(day, generator) {
+        Ok(inputs) => inputs,
+        Err(e) => {
+            eprintln!("Failed to scale part {part}: {e}");
+            return;
+        }
+    };
+
+    println!("{:>12}  {:>12}", "Size", "Time");
+
+    let mut points = vec![];
+
+    for truncated in inputs {
+        let size = truncated.len();
+
+        // solutions are free to panic on inputs that lost required parts.
+        let first_call = catch_silently(|| {
+            let timer = Instant::now();
+            func(&truncated)
+                .into_outcome()
+                .answer()
+                .map(|_| timer.elapsed())
+        });
+
+        let Some(Some(base_time)) = first_call else {
+            println!("{:>12}  {:>12}", format!("{size} B"), "✖");
+            continue;
+        };
+
+        let mean = mean_call_time(&func, truncated.as_str(), &base_time, BUDGET_PER_SIZE);
+        println!("{:>12}  {:>12}", format!("{size} B"), format!("{mean:.1?}"));
+        points.push((size as f64, mean.as_nanos() as f64));
+    }
+
+    match Fit::from_points(&points) {
+        Some(fit) => {
+            println!("Estimate: {fit}");
+            println!(
+                "Projected at {full_size} B: {:.1?}",
+                fit.project(full_size as f64)
+            );
+        }
+        None => println!("Estimate: ✖ the part succeeded at fewer than two sizes."),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Fit, Scaling, Strategy};
+    use std::time::Duration;
+
+    #[test]
+    fn truncates_lines_per_section() {
+        let input = "47|53\n97|13\n97|61\n75|29\n\n75,47,61\n97,61,53,29\n";
+        assert_eq!(
+            Strategy::Lines.truncate(input, 0.5),
+            "47|53\n97|13\n\n75,47,61\n"
+        );
+        assert_eq!(Strategy::Lines.truncate(input, 0.01), "47|53\n\n75,47,61\n");
+    }
+
+    #[test]
+    fn truncates_grids() {
+        let input = "#####\n#S..#\n#...#\n#..E#\n#####\n\n<^>v\n";
+        assert_eq!(
+            Strategy::Grid.truncate(input, 0.25),
+            "###\n#S.\n#..\n\n<^\n"
+        );
+    }
+
+    #[test]
+    fn fits_power_laws() {
+        let quadratic: Vec<(f64, f64)> = [10.0, 20.0, 40.0, 80.0]
+            .iter()
+            .map(|n: &f64| (*n, 3.0 * n * n))
+            .collect();
+        let fit = Fit::from_points(&quadratic).unwrap();
+        assert!((fit.exponent - 2.0).abs() < 1e-9);
+        assert!((fit.r_squared - 1.0).abs() < 1e-9);
+        assert_eq!(fit.class(), "quadratic");
+        assert_eq!(fit.project(160.0), Duration::from_nanos(76800));
+
+        assert_eq!(Fit::from_points(&[(10.0, 5.0)]), None);
+        assert_eq!(Fit::from_points(&[(10.0, 5.0), (10.0, 6.0)]), None);
+    }
+
+    #[test]
+    fn round_trips_args() {
+        let scaling = Scaling {
+            strategy: Strategy::Grid,
+            steps: 4,
+        };
+        let mut args = vec!["16".to_string()];
+        args.extend(scaling.to_args());
+        assert_eq!(Scaling::parse_args(&args), Some(scaling));
+        assert_eq!(scaling.fractions(), vec![0.0625, 0.125, 0.25, 0.5]);
+        assert_eq!(Scaling::parse_args(&["16".to_string()]), None);
+    }
+}
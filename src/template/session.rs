@@ -0,0 +1,267 @@
+/// Module for the session cookie of adventofcode.com, which aoc-cli needs to download inputs and submit answers.
+/// The token is read from the `AOC_SESSION` environment variable, or from the user-level credential file
+/// `~/.adventofcode.session` that `cargo aoc-login` writes and aoc-cli reads by default.
+/// It must never be committed: [`warn_if_committed`] scans the tracked configuration files for it, and [`redact`]
+/// removes it from output and error messages.
+use std::{
+    env,
+    fmt::Display,
+    fs::{self, OpenOptions},
+    io::Write,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+    sync::OnceLock,
+};
+
+use regex::Regex;
+
+use crate::template::{ANSI_BOLD, ANSI_RESET};
+
+pub const ENV_VAR: &str = "AOC_SESSION";
+const FILE_NAME: &str = ".adventofcode.session";
+const REDACTED: &str = "<redacted>";
+/// Shorter values of `AOC_SESSION` can not be real tokens and would match all over the place.
+const MIN_TOKEN_LEN: usize = 32;
+/// Pathspecs of the tracked files that a token ends up in, scanning everything would slow down every command.
+const SCANNED_FILES: [&str; 4] = [".cargo", "*.toml", "*.env", "*.md"];
+
+/// Tokens are 128 hex digits, shorter runs are likely hashes.
+fn token_pattern() -> &'static Regex {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    PATTERN.get_or_init(|| Regex::new(r"\b[0-9a-fA-F]{128}\b").unwrap())
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Source {
+    Env,
+    File(PathBuf),
+}
+
+impl Display for Source {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Source::Env => write!(f, "`{ENV_VAR}`"),
+            Source::File(path) => write!(f, "`{}`", path.display()),
+        }
+    }
+}
+
+pub struct Session {
+    token: String,
+    source: Source,
+}
+
+impl Session {
+    /// Reads the session from `AOC_SESSION`, then from the credential file.
+    pub fn read() -> Option<Session> {
+        if let Some(token) = env::var(ENV_VAR).ok().filter(|t| !t.trim().is_empty()) {
+            return Some(Session {
+                token: token.trim().to_string(),
+                source: Source::Env,
+            });
+        }
+
+        let path = credential_file()?;
+        let token = fs::read_to_string(&path).ok()?.trim().to_string();
+        (!token.is_empty()).then_some(Session {
+            token,
+            source: Source::File(path),
+        })
+    }
+
+    pub fn token(&self) -> &str {
+        &self.token
+    }
+
+    pub fn source(&self) -> &Source {
+        &self.source
+    }
+}
+
+/// Never prints the token, e.g. when a session ends up in an `unwrap`.
+impl std::fmt::Debug for Session {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Session")
+            .field("token", &REDACTED)
+            .field("source", &self.source)
+            .finish()
+    }
+}
+
+/// The user-level credential file, outside of any repository.
+pub fn credential_file() -> Option<PathBuf> {
+    env::var_os("HOME")
+        .or_else(|| env::var_os("USERPROFILE"))
+        .map(|home| PathBuf::from(home).join(FILE_NAME))
+}
+
+/// Validates a pasted session cookie, accepting a leading `session=` as copied from some browsers.
+pub fn parse_token(input: &str) -> Result<String, String> {
+    let token = input.trim();
+    let token = token.strip_prefix("session=").unwrap_or(token);
+
+    if token.is_empty() {
+        return Err("No session cookie given.".into());
+    }
+    if !token.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(
+            "The session cookie should only contain hex digits, copy the value of the `session` cookie.".into(),
+        );
+    }
+    Ok(token.to_string())
+}
+
+/// Stores the token in the credential file, readable by the current user only.
+pub fn store(token: &str) -> Result<PathBuf, String> {
+    let path = credential_file().ok_or("Could not find the home directory.")?;
+
+    let mut options = OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    // a new file is created private, so the token is never readable by others.
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+
+    let mut file = options
+        .open(&path)
+        .map_err(|e| format!("Failed to open `{}`: {e}", path.display()))?;
+
+    // `mode` only applies to new files, restrict an existing one before writing to it.
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        file.set_permissions(fs::Permissions::from_mode(0o600))
+            .map_err(|e| format!("Failed to restrict `{}`: {e}", path.display()))?;
+    }
+
+    writeln!(file, "{token}").map_err(|e| format!("Failed to write `{}`: {e}", path.display()))?;
+
+    Ok(path)
+}
+
+/// Replaces the current session token, and anything that looks like one, with `<redacted>`.
+pub fn redact(text: &str) -> String {
+    redact_token(text, Session::read().as_ref().map(Session::token))
+}
+
+fn known_token(token: Option<&str>) -> Option<&str> {
+    token.filter(|token| token.len() >= MIN_TOKEN_LEN)
+}
+
+pub(crate) fn redact_token(text: &str, token: Option<&str>) -> String {
+    let text = match known_token(token) {
+        Some(token) => text.replace(token, REDACTED),
+        None => text.to_string(),
+    };
+    token_pattern().replace_all(&text, REDACTED).into_owned()
+}
+
+/// Lines of `text` that contain the token or anything that looks like one, starting at 1.
+fn find_tokens(text: &str, token: Option<&str>) -> Vec<usize> {
+    text.lines()
+        .enumerate()
+        .filter(|(_, line)| {
+            known_token(token).is_some_and(|token| line.contains(token))
+                || token_pattern().is_match(line)
+        })
+        .map(|(i, _)| i + 1)
+        .collect()
+}
+
+/// Locations (`path:line`) of session tokens in the configuration and documentation files tracked by git.
+pub fn find_in_tracked_files() -> Vec<String> {
+    let Ok(output) = Command::new("git")
+        .args(["ls-files", "-z", "--"])
+        .args(SCANNED_FILES)
+        .stderr(Stdio::null())
+        .output()
+    else {
+        return vec![];
+    };
+    if !output.status.success() {
+        return vec![];
+    }
+
+    let session = Session::read();
+    let token = session.as_ref().map(Session::token);
+
+    String::from_utf8_lossy(&output.stdout)
+        .split('\0')
+        .filter(|path| !path.is_empty())
+        .flat_map(|path| {
+            let lines = fs::read(Path::new(path))
+                .map(|bytes| find_tokens(&String::from_utf8_lossy(&bytes), token))
+                .unwrap_or_default();
+            lines.into_iter().map(move |line| format!("{path}:{line}"))
+        })
+        .collect()
+}
+
+/// Prints a warning for every session token found in the tracked files.
+pub fn warn_if_committed() {
+    let locations = find_in_tracked_files();
+    if locations.is_empty() {
+        return;
+    }
+
+    eprintln!(
+        "{ANSI_BOLD}⚠️  WARNING: a session token is committed to this repository:{ANSI_RESET}"
+    );
+    for location in &locations {
+        eprintln!("  {location}");
+    }
+    eprintln!(
+        "{ANSI_BOLD}Anyone who can read the repository can use your Advent of Code account.{ANSI_RESET}"
+    );
+    eprintln!(
+        "Remove the token, store it with `cargo aoc-login` instead, and log out of adventofcode.com to invalidate it.\n"
+    );
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{find_tokens, parse_token, redact_token};
+
+    #[test]
+    fn parses_tokens() {
+        let token = "ab".repeat(64);
+        assert_eq!(parse_token(&format!(" {token}\n")), Ok(token.clone()));
+        assert_eq!(parse_token(&format!("session={token}")), Ok(token));
+        assert!(parse_token("\n").is_err());
+        assert!(parse_token("not a token").is_err());
+    }
+
+    #[test]
+    fn redacts_tokens() {
+        let token = "ab".repeat(64);
+        assert_eq!(
+            redact_token(&format!("cookie: session={token}; path=/"), None),
+            "cookie: session=<redacted>; path=/"
+        );
+        let short = "1a2b".repeat(8);
+        assert_eq!(
+            redact_token(&format!("Loaded session cookie {short}"), Some(&short)),
+            "Loaded session cookie <redacted>"
+        );
+        assert_eq!(redact_token("day 01, part 1", Some("1")), "day 01, part 1");
+        let hash = "ab".repeat(63);
+        assert_eq!(redact_token(&hash, None), hash);
+        let digits = "1".repeat(95);
+        assert_eq!(redact_token(&digits, None), digits);
+    }
+
+    #[test]
+    fn finds_committed_tokens() {
+        let token = "cd".repeat(64);
+        let config = format!("[env]\nAOC_USER_ID = \"1\"\nAOC_SESSION = \"{token}\"\n");
+        assert_eq!(find_tokens(&config, None), vec![3]);
+        let secret = "secret".repeat(6);
+        assert_eq!(
+            find_tokens(&format!("a\n{secret}\n"), Some(&secret)),
+            vec![2]
+        );
+        assert_eq!(find_tokens("a\nb\n", Some("a")), Vec::<usize>::new());
+        assert_eq!(find_tokens(&"f".repeat(64), None), Vec::<usize>::new());
+    }
+}
@@ -0,0 +1,323 @@
+/// Module for the [`Solution`] trait, which describes the solution of one day.
+/// Days either implement it, see `solution!(solution = Day14)`, or keep free `part_one` / `part_two`
+/// functions, which the `solution!` macro wraps in [`Parts`].
+use std::{fmt::Display, rc::Rc};
+
+use crate::template::parse::InputError;
+use crate::template::registry::Implementation;
+use crate::template::Day;
+
+/// Result of a part: answered, unsolved, or failed on the input.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Outcome<T> {
+    Solved(T),
+    Unsolved,
+    Failed(InputError),
+}
+
+impl<T> Outcome<T> {
+    /// The answer, `None` if the part is unsolved or failed.
+    pub fn answer(self) -> Option<T> {
+        match self {
+            Outcome::Solved(answer) => Some(answer),
+            Outcome::Unsolved | Outcome::Failed(_) => None,
+        }
+    }
+
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> Outcome<U> {
+        match self {
+            Outcome::Solved(answer) => Outcome::Solved(f(answer)),
+            Outcome::Unsolved => Outcome::Unsolved,
+            Outcome::Failed(error) => Outcome::Failed(error),
+        }
+    }
+
+    /// Resolves the location of an error in the raw `input`, see [`InputError::locate`].
+    #[must_use]
+    pub fn locate(self, input: &str) -> Self {
+        match self {
+            Outcome::Failed(error) => Outcome::Failed(error.locate(input)),
+            outcome => outcome,
+        }
+    }
+}
+
+/// What a part returns: `Option<T>`, or `Result<T, InputError>` for parts that can fail on the input.
+pub trait Answer {
+    type Value: Display;
+
+    fn into_outcome(self) -> Outcome<Self::Value>;
+}
+
+impl<T: Display> Answer for Option<T> {
+    type Value = T;
+
+    fn into_outcome(self) -> Outcome<T> {
+        self.map_or(Outcome::Unsolved, Outcome::Solved)
+    }
+}
+
+impl<T: Display> Answer for Result<T, InputError> {
+    type Value = T;
+
+    fn into_outcome(self) -> Outcome<T> {
+        self.map_or_else(Outcome::Failed, Outcome::Solved)
+    }
+}
+
+impl<T: Display> Answer for Outcome<T> {
+    type Value = T;
+
+    fn into_outcome(self) -> Outcome<T> {
+        self
+    }
+}
+
+/// The solution of one day, with a parse step that is shared by both parts.
+pub trait Solution {
+    /// The day of the puzzle.
+    const DAY: Day;
+
+    /// The title of the puzzle, e.g. `Historian Hysteria`.
+    const TITLE: Option<&'static str> = None;
+
+    /// Whether [`Solution::parse`] is timed as its own step, before the parts.
+    const TIME_PARSE: bool = true;
+
+    /// The parsed input. It may borrow from the raw input.
+    type Input<'a>;
+    /// What part one returns, e.g. `Option<u64>` or `Result<u64, InputError>`.
+    type Answer1: Answer;
+    type Answer2: Answer;
+
+    fn parse<'a>(&self, input: &'a str) -> Result<Self::Input<'a>, InputError>;
+
+    /// Parses an example. Override this if the examples use other parameters
+    /// than the real input, e.g. a smaller grid.
+    fn parse_example<'a>(&self, input: &'a str) -> Result<Self::Input<'a>, InputError> {
+        self.parse(input)
+    }
+
+    fn part_one(&self, input: &Self::Input<'_>) -> Self::Answer1;
+
+    fn part_two(&self, input: &Self::Input<'_>) -> Self::Answer2;
+
+    /// Whether a part is implemented. Parts that are not are skipped when running the solution.
+    fn has_part(&self, _part: u8) -> bool {
+        true
+    }
+}
+
+/// Outcomes of both parts of a solution.
+pub type Outcomes<S> = (
+    Outcome<<<S as Solution>::Answer1 as Answer>::Value>,
+    Outcome<<<S as Solution>::Answer2 as Answer>::Value>,
+);
+
+/// Solves both parts of `input`. Errors are located in `input`, and fail both parts if parsing fails.
+pub fn solve<S: Solution>(solution: &S, input: &str) -> Outcomes<S> {
+    let parsed = match solution.parse(input) {
+        Ok(parsed) => parsed,
+        Err(error) => {
+            let error = error.locate(input);
+            return (Outcome::Failed(error.clone()), Outcome::Failed(error));
+        }
+    };
+
+    let part_one = if solution.has_part(1) {
+        solution.part_one(&parsed).into_outcome().locate(input)
+    } else {
+        Outcome::Unsolved
+    };
+    let part_two = if solution.has_part(2) {
+        solution.part_two(&parsed).into_outcome().locate(input)
+    } else {
+        Outcome::Unsolved
+    };
+    (part_one, part_two)
+}
+
+/// Parses `input` and runs one part on it, for implementations that start from the raw input.
+pub(crate) fn parse_and_solve<S: Solution, A: Answer>(
+    solution: &S,
+    input: &str,
+    part: impl Fn(&S::Input<'_>) -> A,
+) -> Outcome<A::Value> {
+    match solution.parse(input) {
+        Ok(parsed) => part(&parsed).into_outcome(),
+        Err(error) => Outcome::Failed(error),
+    }
+}
+
+/// The implemented parts of a solution, each parsing the raw input on its own.
+pub fn implementations<S: Solution + 'static>(solution: S) -> Vec<Implementation> {
+    let solution = Rc::new(solution);
+    let mut implementations = vec![];
+
+    if solution.has_part(1) {
+        let solution = Rc::clone(&solution);
+        implementations.push(Implementation::new(1, "part_one", move |input| {
+            parse_and_solve(&*solution, input, |parsed| solution.part_one(parsed))
+        }));
+    }
+
+    if solution.has_part(2) {
+        implementations.push(Implementation::new(2, "part_two", move |input| {
+            parse_and_solve(&*solution, input, |parsed| solution.part_two(parsed))
+        }));
+    }
+
+    implementations
+}
+
+/// Free `part_one` / `part_two` functions as a [`Solution`], see `solution!`.
+/// The parts take the raw input, so there is no separate parse step.
+/// `A` and `B` are what the parts return, see [`Answer`].
+pub struct Parts<const DAY: u8, A = Option<u8>, B = Option<u8>> {
+    part_one: Option<fn(&str) -> A>,
+    part_two: Option<fn(&str) -> B>,
+}
+
+impl<const DAY: u8> Default for Parts<DAY> {
+    fn default() -> Self {
+        Parts {
+            part_one: None,
+            part_two: None,
+        }
+    }
+}
+
+impl<const DAY: u8, A, B> Parts<DAY, A, B> {
+    pub fn with_part_one<T>(self, func: fn(&str) -> T) -> Parts<DAY, T, B> {
+        Parts {
+            part_one: Some(func),
+            part_two: self.part_two,
+        }
+    }
+
+    pub fn with_part_two<T>(self, func: fn(&str) -> T) -> Parts<DAY, A, T> {
+        Parts {
+            part_one: self.part_one,
+            part_two: Some(func),
+        }
+    }
+}
+
+impl<const DAY: u8, A: Answer, B: Answer> Solution for Parts<DAY, A, B> {
+    const DAY: Day = Day::__new_unchecked(DAY);
+    const TIME_PARSE: bool = false;
+
+    type Input<'a> = &'a str;
+    type Answer1 = Outcome<A::Value>;
+    type Answer2 = Outcome<B::Value>;
+
+    fn parse<'a>(&self, input: &'a str) -> Result<&'a str, InputError> {
+        Ok(input)
+    }
+
+    fn part_one(&self, input: &&str) -> Outcome<A::Value> {
+        self.part_one
+            .map_or(Outcome::Unsolved, |func| func(input).into_outcome())
+    }
+
+    fn part_two(&self, input: &&str) -> Outcome<B::Value> {
+        self.part_two
+            .map_or(Outcome::Unsolved, |func| func(input).into_outcome())
+    }
+
+    fn has_part(&self, part: u8) -> bool {
+        if part == 1 {
+            self.part_one.is_some()
+        } else {
+            self.part_two.is_some()
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{implementations, solve, Outcome, Parts, Solution};
+    use crate::day;
+    use crate::template::parse::{InputError, ParseExt};
+    use crate::template::Day;
+
+    /// Sums the numbers of the input, or of the first line of an example.
+    struct Sum;
+
+    impl Solution for Sum {
+        const DAY: Day = day!(1);
+
+        type Input<'a> = Vec<u64>;
+        type Answer1 = Option<u64>;
+        type Answer2 = Result<usize, InputError>;
+
+        fn parse(&self, input: &str) -> Result<Vec<u64>, InputError> {
+            input.split_whitespace().map(ParseExt::try_parse).collect()
+        }
+
+        fn parse_example(&self, input: &str) -> Result<Vec<u64>, InputError> {
+            self.parse(input.lines().next().unwrap_or_default())
+        }
+
+        fn part_one(&self, input: &Vec<u64>) -> Option<u64> {
+            Some(input.iter().sum())
+        }
+
+        fn part_two(&self, input: &Vec<u64>) -> Result<usize, InputError> {
+            match input.len() {
+                0 => Err(InputError::new("expected numbers")),
+                len => Ok(len),
+            }
+        }
+    }
+
+    fn count_lines(input: &str) -> Option<usize> {
+        Some(input.lines().count())
+    }
+
+    #[test]
+    fn solves_trait_solutions() {
+        assert_eq!(
+            solve(&Sum, "1 2\n3\n"),
+            (Outcome::Solved(6), Outcome::Solved(3))
+        );
+        assert_eq!(
+            Sum.part_one(&Sum.parse_example("1 2\n3\n").unwrap()),
+            Some(3)
+        );
+
+        let parts = implementations(Sum);
+        assert_eq!(parts.len(), 2);
+        assert_eq!(parts[0].solve("4 5"), Outcome::Solved("9".into()));
+        assert!(matches!(parts[1].solve(""), Outcome::Failed(_)));
+    }
+
+    #[test]
+    fn locates_parse_errors() {
+        let (part_one, _) = solve(&Sum, "1 2\n3 x\n");
+        let Outcome::Failed(error) = part_one else {
+            panic!("expected a parse error");
+        };
+        assert_eq!(error.to_string(), "line 2, column 3: expected u64, got `x`");
+    }
+
+    #[test]
+    fn adapts_free_functions() {
+        let parts = Parts::<3>::default().with_part_two(count_lines);
+        assert_eq!(
+            <Parts<3, Option<u8>, Option<usize>> as Solution>::DAY,
+            day!(3)
+        );
+        assert!(!parts.has_part(1));
+        assert_eq!(
+            solve(&parts, "a\nb\n"),
+            (Outcome::Unsolved, Outcome::Solved(2))
+        );
+
+        let implementations = implementations(parts);
+        assert_eq!(implementations.len(), 1);
+        assert_eq!(implementations[0].part, 2);
+    }
+}
@@ -0,0 +1,232 @@
+/// Module that times named phases of a solution, see [`crate::aoc_span`].
+/// Spans are only recorded when solutions are built with the `spans` feature,
+/// otherwise they compile to nothing.
+#[cfg(any(feature = "spans", feature = "test_lib"))]
+use std::time::Duration;
+
+/// Creates a named timing span that lasts until the returned guard is dropped.
+///
+/// ```ignore
+/// let _span = aoc_span!("dijkstra");
+/// let cost = aoc_span!("backtrack", grid.backtrack(min_cost));
+/// ```
+#[macro_export]
+macro_rules! aoc_span {
+    ($name:expr) => {
+        $crate::template::spans::enter($name)
+    };
+    ($name:expr, $body:expr) => {{
+        let _span = $crate::template::spans::enter($name);
+        $body
+    }};
+}
+
+/// Guard of an entered span.
+#[cfg(not(feature = "spans"))]
+pub struct Span;
+
+#[cfg(not(feature = "spans"))]
+#[inline]
+pub fn enter(_name: &'static str) -> Span {
+    Span
+}
+
+#[cfg(feature = "spans")]
+pub use recording::{enter, record, Span};
+
+#[cfg(feature = "spans")]
+mod recording {
+    use super::Recorder;
+    use std::{cell::RefCell, time::Instant};
+
+    thread_local! {
+        static RECORDER: RefCell<Option<Recorder>> = const { RefCell::new(None) };
+    }
+
+    /// Guard of an entered span.
+    pub struct Span {
+        node: Option<usize>,
+        start: Instant,
+    }
+
+    pub fn enter(name: &'static str) -> Span {
+        let node = RECORDER.with(|r| r.borrow_mut().as_mut().and_then(|r| r.enter(name)));
+        Span {
+            node,
+            start: Instant::now(),
+        }
+    }
+
+    impl Drop for Span {
+        fn drop(&mut self) {
+            if let Some(node) = self.node {
+                let elapsed = self.start.elapsed();
+                RECORDER.with(|r| {
+                    if let Some(r) = r.borrow_mut().as_mut() {
+                        r.exit(node, elapsed);
+                    }
+                });
+            }
+        }
+    }
+
+    /// Runs `f` and records the spans it enters on the current thread.
+    pub fn record<T>(f: impl FnOnce() -> T) -> (T, Recorder) {
+        RECORDER.with(|r| *r.borrow_mut() = Some(Recorder::default()));
+        let result = f();
+        let recorder = RECORDER.with(|r| r.borrow_mut().take()).unwrap_or_default();
+        (result, recorder)
+    }
+}
+
+#[cfg(any(feature = "spans", feature = "test_lib"))]
+struct Node {
+    name: &'static str,
+    parent: Option<usize>,
+    calls: u64,
+    total: Duration,
+}
+
+/// Aggregates entered spans into a tree. Spans with the same name and parent are merged.
+#[cfg(any(feature = "spans", feature = "test_lib"))]
+#[derive(Default)]
+pub struct Recorder {
+    nodes: Vec<Node>,
+    stack: Vec<usize>,
+}
+
+#[cfg(any(feature = "spans", feature = "test_lib"))]
+impl Recorder {
+    fn enter(&mut self, name: &'static str) -> Option<usize> {
+        // recursive spans are already covered by their outermost call.
+        if self.stack.iter().any(|i| self.nodes[*i].name == name) {
+            return None;
+        }
+
+        let parent = self.stack.last().copied();
+        let node = match self
+            .nodes
+            .iter()
+            .position(|n| n.parent == parent && n.name == name)
+        {
+            Some(i) => i,
+            None => {
+                self.nodes.push(Node {
+                    name,
+                    parent,
+                    calls: 0,
+                    total: Duration::ZERO,
+                });
+                self.nodes.len() - 1
+            }
+        };
+
+        self.stack.push(node);
+        Some(node)
+    }
+
+    fn exit(&mut self, node: usize, elapsed: Duration) {
+        if let Some(pos) = self.stack.iter().rposition(|i| *i == node) {
+            self.stack.truncate(pos);
+        }
+        self.nodes[node].calls += 1;
+        self.nodes[node].total += elapsed;
+    }
+
+    /// Renders the spans as a tree, with each span's share of the `total` runtime.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn render(&self, total: Duration) -> String {
+        let mut rows: Vec<(String, &Node)> = vec![];
+        self.render_children(None, "", &mut rows);
+
+        let width = rows
+            .iter()
+            .map(|(l, _)| l.chars().count())
+            .max()
+            .unwrap_or(0);
+
+        rows.iter()
+            .map(|(label, node)| {
+                let share = if total.is_zero() {
+                    0.0
+                } else {
+                    node.total.as_nanos() as f64 / total.as_nanos() as f64 * 100.0
+                };
+                let mut line = format!(
+                    "{label}{}  {:>10}  {share:>5.1}%",
+                    " ".repeat(width - label.chars().count()),
+                    format!("{:.1?}", node.total),
+                );
+                if node.calls > 1 {
+                    line.push_str(&format!("  ×{}", node.calls));
+                }
+                line
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn render_children<'a>(
+        &'a self,
+        parent: Option<usize>,
+        indent: &str,
+        rows: &mut Vec<(String, &'a Node)>,
+    ) {
+        let children: Vec<usize> = (0..self.nodes.len())
+            .filter(|i| self.nodes[*i].parent == parent)
+            .collect();
+
+        for (n, child) in children.iter().enumerate() {
+            let is_last = n == children.len() - 1;
+            let branch = if is_last { "└─ " } else { "├─ " };
+            rows.push((
+                format!("  {indent}{branch}{}", self.nodes[*child].name),
+                &self.nodes[*child],
+            ));
+
+            let indent = format!("{indent}{}", if is_last { "   " } else { "│  " });
+            self.render_children(Some(*child), &indent, rows);
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Recorder;
+    use std::time::Duration;
+
+    #[test]
+    fn renders_span_trees() {
+        let mut recorder = Recorder::default();
+
+        let parse = recorder.enter("parse").unwrap();
+        recorder.exit(parse, Duration::from_millis(1));
+
+        let search = recorder.enter("dijkstra").unwrap();
+        for _ in 0..3 {
+            let pop = recorder.enter("pop").unwrap();
+            assert_eq!(recorder.enter("pop"), None);
+            recorder.exit(pop, Duration::from_millis(1));
+        }
+        recorder.exit(search, Duration::from_millis(6));
+
+        let backtrack = recorder.enter("backtrack").unwrap();
+        recorder.exit(backtrack, Duration::from_millis(2));
+
+        let expected = [
+            "  ├─ parse           1.0ms   10.0%",
+            "  ├─ dijkstra        6.0ms   60.0%",
+            "  │  └─ pop          3.0ms   30.0%  ×3",
+            "  └─ backtrack       2.0ms   20.0%",
+        ]
+        .join("\n");
+        assert_eq!(recorder.render(Duration::from_millis(10)), expected);
+    }
+
+    #[test]
+    fn renders_nothing_without_spans() {
+        assert_eq!(Recorder::default().render(Duration::from_millis(1)), "");
+    }
+}
@@ -1,19 +1,249 @@
-use std::{collections::HashMap, fs, io::Error, str::FromStr};
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
 use tinyjson::JsonValue;
 
+use crate::template::alloc::Allocations;
+use crate::template::config;
+use crate::template::fingerprint::{self, Fingerprint};
 use crate::template::Day;
 
-static TIMINGS_FILE_PATH: &str = "./data/timings.json";
+static TIMINGS_FILE_NAME: &str = "timings.json";
+static HISTORY_FILE_NAME: &str = "timings_history.json";
+
+fn data_path(name: &str) -> PathBuf {
+    config::get().paths.data.join(name)
+}
+
+/// Number of runs per day that are kept in the history.
+const HISTORY_LIMIT: usize = 50;
+
+/// Version of the JSON schema written by [`Timings::store_file`].
+///  1. (unversioned) parts are stored as display strings, e.g. `"74.13ms"`.
+///  2. parts are stored as numeric nanoseconds and sample counts, plus run metadata.
+///  3. parts store their cold time and allocations, days their merge strategy, fingerprint and counters.
+pub const SCHEMA_VERSION: u32 = 3;
+
+#[derive(Debug)]
+pub enum Error {
+    IO(io::Error),
+    /// The timings file exists but could not be read. Contains the reason.
+    Corrupt(String),
+    /// The timings file was written by a newer version of this template.
+    UnsupportedVersion(u32),
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::IO(e) => write!(f, "could not access file: {e}"),
+            Error::Corrupt(reason) => write!(f, "file is corrupt: {reason}"),
+            Error::UnsupportedVersion(v) => write!(
+                f,
+                "file uses schema version {v}, but only versions up to {SCHEMA_VERSION} are supported."
+            ),
+        }
+    }
+}
+
+/// Benchmark result for a single part (or the parse step) of a solution.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PartTiming {
+    /// Average execution time in nanoseconds.
+    pub nanos: f64,
+    /// Number of samples the average was computed from. `0` if unknown, e.g. for migrated data.
+    pub samples: u64,
+    /// Execution time of the first call in nanoseconds, before caches and the allocator were warm.
+    pub cold_nanos: Option<f64>,
+    /// Heap usage of the first call, if allocations were counted.
+    pub allocations: Option<Allocations>,
+}
+
+impl PartTiming {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub fn duration(&self) -> Duration {
+        Duration::from_nanos(self.nanos as u64)
+    }
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub fn cold_duration(&self) -> Option<Duration> {
+        self.cold_nanos.map(|x| Duration::from_nanos(x as u64))
+    }
+}
+
+impl Display for PartTiming {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:.1?}", self.duration())
+    }
+}
+
+/// Value of a named counter of a part, see `aoc_count!`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Counter {
+    pub part: u8,
+    pub name: String,
+    pub value: u64,
+}
 
 /// Represents benchmark times for a single day.
 #[derive(Clone, Debug)]
 pub struct Timing {
     pub day: Day,
-    pub part_1: Option<String>,
-    pub part_2: Option<String>,
+    pub parse: Option<PartTiming>,
+    pub part_1: Option<PartTiming>,
+    pub part_2: Option<PartTiming>,
+    /// Peak heap usage in bytes, if it was measured.
+    pub peak_memory: Option<u64>,
+    /// Unix timestamp (seconds) of the run that produced this timing, if known.
+    pub recorded_at: Option<u64>,
+    /// Strategy that produced this entry when it was merged into the stored timings.
+    pub strategy: Option<MergeStrategy>,
+    /// Machine and toolchain the timing was recorded on, if known.
+    pub fingerprint: Option<Fingerprint>,
+    /// Counters bumped by the parts, if the run was built with counters.
+    pub counters: Vec<Counter>,
     pub total_nanos: f64,
 }
 
+impl Timing {
+    /// Creates an empty timing for a day.
+    pub fn new(day: Day) -> Self {
+        Timing {
+            day,
+            parse: None,
+            part_1: None,
+            part_2: None,
+            peak_memory: None,
+            recorded_at: None,
+            strategy: None,
+            fingerprint: None,
+            counters: vec![],
+            total_nanos: 0_f64,
+        }
+    }
+
+    /// Whether this timing was recorded on a machine comparable to `other`'s.
+    pub fn is_comparable(&self, other: &Timing) -> bool {
+        fingerprint::is_comparable(self.fingerprint.as_ref(), other.fingerprint.as_ref())
+    }
+
+    /// Recomputes `total_nanos` from the parse step and parts.
+    fn update_total(&mut self) {
+        self.total_nanos = [self.parse, self.part_1, self.part_2]
+            .iter()
+            .flatten()
+            .map(|p| p.nanos)
+            .sum();
+    }
+
+    pub fn parse_nanos(&self) -> Option<f64> {
+        self.parse.map(|p| p.nanos)
+    }
+
+    pub fn part_1_nanos(&self) -> Option<f64> {
+        self.part_1.map(|p| p.nanos)
+    }
+
+    pub fn part_2_nanos(&self) -> Option<f64> {
+        self.part_2.map(|p| p.nanos)
+    }
+}
+
+/// Parses a duration formatted via `{:?}` (e.g. `74.13ms`) to nanoseconds.
+pub fn parse_duration(s: &str) -> Option<f64> {
+    // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
+    match s.trim() {
+        s if s.contains("ns") => parse_to_float(s, "ns"),
+        s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
+        s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
+        s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
+    }
+}
+
+fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
+    s.split(postfix).next()?.parse().ok()
+}
+
+/// Current unix timestamp in seconds.
+pub fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+/// Decides which measurement is kept when new timings are merged into stored ones.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MergeStrategy {
+    /// Always keep the newest run.
+    #[default]
+    Latest,
+    /// Keep the fastest measurement per part.
+    Minimum,
+    /// Keep the median measurement per part over the last N runs.
+    Median(usize),
+}
+
+/// Number of runs considered by `median` if no window size is given.
+pub const DEFAULT_MEDIAN_WINDOW: usize = 5;
+
+impl FromStr for MergeStrategy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once(':') {
+            None if s == "latest" => Ok(MergeStrategy::Latest),
+            None if s == "min" => Ok(MergeStrategy::Minimum),
+            None if s == "median" => Ok(MergeStrategy::Median(DEFAULT_MEDIAN_WINDOW)),
+            Some(("median", n)) => match n.parse() {
+                Ok(n) if n > 0 => Ok(MergeStrategy::Median(n)),
+                _ => Err(format!(
+                    "invalid median window `{n}`, expected a positive number."
+                )),
+            },
+            _ => Err(format!(
+                "unknown merge strategy `{s}`, expected `latest`, `min` or `median[:N]`."
+            )),
+        }
+    }
+}
+
+impl Display for MergeStrategy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MergeStrategy::Latest => write!(f, "latest"),
+            MergeStrategy::Minimum => write!(f, "min"),
+            MergeStrategy::Median(n) => write!(f, "median:{n}"),
+        }
+    }
+}
+
+/// Picks the median of some part timings, preferring the lower one for even counts.
+fn median_part(mut parts: Vec<PartTiming>) -> Option<PartTiming> {
+    if parts.is_empty() {
+        return None;
+    }
+    parts.sort_by(|a, b| a.nanos.total_cmp(&b.nanos));
+    Some(parts[(parts.len() - 1) / 2])
+}
+
+fn min_part(a: Option<PartTiming>, b: Option<PartTiming>) -> Option<PartTiming> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(if b.nanos < a.nanos { b } else { a }),
+        (a, b) => a.or(b),
+    }
+}
+
 /// Represents benchmark times for a set of days.
 /// Can be serialized from / to JSON.
 #[derive(Clone, Debug, Default)]
@@ -24,17 +254,98 @@ pub struct Timings {
 impl Timings {
     /// Dehydrate timings to a JSON file.
     pub fn store_file(&self) -> Result<(), Error> {
-        let json = JsonValue::from(self.clone());
-        let mut file = fs::File::create(TIMINGS_FILE_PATH)?;
-        json.format_to(&mut file)
+        Self::store_path(self, &data_path(TIMINGS_FILE_NAME))
     }
 
     /// Rehydrate timings from a JSON file. If not present, returns empty timings.
-    pub fn read_from_file() -> Self {
-        fs::read_to_string(TIMINGS_FILE_PATH)
-            .map_err(|x| x.to_string())
-            .and_then(Timings::try_from)
-            .unwrap_or_default()
+    /// Files written with an older schema are migrated, unreadable files are reported as an error.
+    pub fn read_from_file() -> Result<Self, Error> {
+        Self::read_path(&data_path(TIMINGS_FILE_NAME))
+    }
+
+    /// Dehydrate the history of past runs to a JSON file.
+    pub fn store_history(&self) -> Result<(), Error> {
+        Self::store_path(self, &data_path(HISTORY_FILE_NAME))
+    }
+
+    /// Rehydrate the history of past runs, in the order they were recorded.
+    pub fn read_history() -> Result<Self, Error> {
+        Self::read_path(&data_path(HISTORY_FILE_NAME))
+    }
+
+    /// Append runs to a history, dropping the oldest runs of a day beyond [`HISTORY_LIMIT`].
+    pub fn append_history(&mut self, runs: &Self) {
+        self.data.extend(runs.data.iter().cloned());
+
+        for run in &runs.data {
+            let count = self.data.iter().filter(|t| t.day == run.day).count();
+            let mut excess = count.saturating_sub(HISTORY_LIMIT);
+            self.data.retain(|t| {
+                if excess > 0 && t.day == run.day {
+                    excess -= 1;
+                    false
+                } else {
+                    true
+                }
+            });
+        }
+    }
+
+    fn store_path(&self, path: &Path) -> Result<(), Error> {
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(path)?;
+        Ok(json.format_to(&mut file)?)
+    }
+
+    fn read_path(path: &Path) -> Result<Self, Error> {
+        match fs::read_to_string(path) {
+            Ok(contents) => Timings::from_json(&contents),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Timings::default()),
+            Err(e) => Err(Error::IO(e)),
+        }
+    }
+
+    /// Parses a timings document of any supported schema version.
+    pub fn from_json(value: &str) -> Result<Self, Error> {
+        let json = JsonValue::from_str(value).map_err(|e| Error::Corrupt(e.to_string()))?;
+
+        let document = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or_else(|| Error::Corrupt("expected JSON document to be an object.".into()))?;
+
+        let version = match document.get("version") {
+            None => 1,
+            Some(v) => v
+                .get::<f64>()
+                .filter(|v| v.fract() == 0.0 && **v >= 1.0)
+                .map(|v| {
+                    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+                    let v = *v as u32;
+                    v
+                })
+                .ok_or_else(|| Error::Corrupt("expected `version` to be an integer.".into()))?,
+        };
+
+        let parse_timing: fn(&JsonValue) -> Result<Timing, String> = match version {
+            1 => migrations::timing_from_v1,
+            2 => migrations::timing_from_v2,
+            SCHEMA_VERSION => |v| Timing::try_from(v),
+            v => return Err(Error::UnsupportedVersion(v)),
+        };
+
+        let json_data = document
+            .get("data")
+            .ok_or_else(|| Error::Corrupt("expected JSON document to have key `data`.".into()))?
+            .get::<Vec<JsonValue>>()
+            .ok_or_else(|| Error::Corrupt("expected `json.data` to be an array.".into()))?;
+
+        Ok(Timings {
+            data: json_data
+                .iter()
+                .map(parse_timing)
+                .collect::<Result<_, _>>()
+                .map_err(Error::Corrupt)?,
+        })
     }
 
     /// Merge two sets of timings, overwriting `self` with `other` if present.
@@ -51,10 +362,60 @@ impl Timings {
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
+        data.sort_unstable_by_key(|a| a.day);
         Timings { data }
     }
 
+    /// Merge two sets of timings, combining days present in both according to `strategy`.
+    /// `history` holds previous runs (including `new`) and is consulted by the median strategy.
+    /// Only runs with a matching fingerprint are combined.
+    pub fn merge_with(&self, new: &Self, strategy: MergeStrategy, history: &Timings) -> Self {
+        let combined = new
+            .data
+            .iter()
+            .map(|timing| {
+                let stored = self.data.iter().find(|t| t.day == timing.day);
+
+                let mut result = match strategy {
+                    MergeStrategy::Latest => timing.clone(),
+                    MergeStrategy::Minimum => {
+                        let mut result = timing.clone();
+                        if let Some(stored) = stored.filter(|s| s.is_comparable(timing)) {
+                            result.parse = min_part(stored.parse, timing.parse);
+                            result.part_1 = min_part(stored.part_1, timing.part_1);
+                            result.part_2 = min_part(stored.part_2, timing.part_2);
+                        }
+                        result
+                    }
+                    MergeStrategy::Median(window) => {
+                        let runs: Vec<&Timing> = history
+                            .data
+                            .iter()
+                            .filter(|t| t.day == timing.day && t.is_comparable(timing))
+                            .rev()
+                            .take(window)
+                            .collect();
+                        let median = |f: fn(&Timing) -> Option<PartTiming>| {
+                            median_part(runs.iter().filter_map(|t| f(t)).collect()).or(f(timing))
+                        };
+
+                        let mut result = timing.clone();
+                        result.parse = median(|t| t.parse);
+                        result.part_1 = median(|t| t.part_1);
+                        result.part_2 = median(|t| t.part_2);
+                        result
+                    }
+                };
+
+                result.update_total();
+                result.strategy = Some(strategy);
+                result
+            })
+            .collect();
+
+        self.merge(&Timings { data: combined })
+    }
+
     /// Sum up total duration of timings as millis.
     pub fn total_millis(&self) -> f64 {
         self.data.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64
@@ -73,6 +434,7 @@ impl From<Timings> for JsonValue {
     fn from(value: Timings) -> Self {
         let mut map: HashMap<String, JsonValue> = HashMap::new();
 
+        map.insert("version".into(), JsonValue::Number(SCHEMA_VERSION.into()));
         map.insert(
             "data".into(),
             JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
@@ -82,30 +444,165 @@ impl From<Timings> for JsonValue {
     }
 }
 
-impl TryFrom<String> for Timings {
+/* -------------------------------------------------------------------------- */
+
+fn opt_to_json<T>(value: Option<T>, f: impl Fn(T) -> JsonValue) -> JsonValue {
+    match value {
+        Some(x) => f(x),
+        None => JsonValue::Null,
+    }
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn u64_to_json(value: u64) -> JsonValue {
+    JsonValue::Number(value as f64)
+}
+
+fn json_to_u64(value: &JsonValue) -> Option<u64> {
+    value
+        .get::<f64>()
+        .filter(|v| v.fract() == 0.0 && **v >= 0.0)
+        .map(|v| {
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            let v = *v as u64;
+            v
+        })
+}
+
+impl From<&PartTiming> for JsonValue {
+    fn from(value: &PartTiming) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+        map.insert("nanos".into(), JsonValue::Number(value.nanos));
+        map.insert("samples".into(), u64_to_json(value.samples));
+        map.insert(
+            "cold_nanos".into(),
+            opt_to_json(value.cold_nanos, JsonValue::Number),
+        );
+        map.insert(
+            "allocations".into(),
+            opt_to_json(value.allocations.as_ref(), JsonValue::from),
+        );
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for PartTiming {
     type Error = String;
 
-    fn try_from(value: String) -> Result<Self, Self::Error> {
-        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected part timing to be a JSON object.")?;
+
+        let nanos = json
+            .get("nanos")
+            .and_then(|v| v.get::<f64>().copied())
+            .filter(|v| v.is_finite() && *v >= 0.0)
+            .ok_or("Expected part timing.nanos to be a positive number.")?;
+
+        let samples = json
+            .get("samples")
+            .and_then(json_to_u64)
+            .ok_or("Expected part timing.samples to be an integer.")?;
+
+        let cold_nanos = match json.get("cold_nanos") {
+            None => {
+                return Err(
+                    "Expected part timing.cold_nanos to be null or a positive number.".into(),
+                )
+            }
+            Some(v) if v.is_null() => None,
+            Some(v) => Some(
+                v.get::<f64>()
+                    .copied()
+                    .filter(|v| v.is_finite() && *v >= 0.0)
+                    .ok_or("Expected part timing.cold_nanos to be null or a positive number.")?,
+            ),
+        };
+
+        let allocations = match json.get("allocations") {
+            None => return Err("Expected part timing.allocations to be null or an object.".into()),
+            Some(v) if v.is_null() => None,
+            Some(v) => Some(Allocations::try_from(v)?),
+        };
+
+        Ok(PartTiming {
+            nanos,
+            samples,
+            cold_nanos,
+            allocations,
+        })
+    }
+}
 
-        let json_data = json
+impl From<&Allocations> for JsonValue {
+    fn from(value: &Allocations) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+        map.insert("count".into(), u64_to_json(value.count));
+        map.insert("bytes".into(), u64_to_json(value.bytes));
+        map.insert("peak".into(), u64_to_json(value.peak));
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Allocations {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
             .get::<HashMap<String, JsonValue>>()
-            .ok_or("expected JSON document to be an object.")?
-            .get("data")
-            .ok_or("expected JSON document to have key `data`.")?
-            .get::<Vec<JsonValue>>()
-            .ok_or("expected `json.data` to be an array.")?;
+            .ok_or("Expected part timing.allocations to be a JSON object.")?;
 
-        Ok(Timings {
-            data: json_data
-                .iter()
-                .map(Timing::try_from)
-                .collect::<Result<_, _>>()?,
+        let get_u64 = |key: &str| {
+            json.get(key)
+                .and_then(json_to_u64)
+                .ok_or(format!("Expected allocations.{key} to be an integer."))
+        };
+
+        Ok(Allocations {
+            count: get_u64("count")?,
+            bytes: get_u64("bytes")?,
+            peak: get_u64("peak")?,
         })
     }
 }
 
-/* -------------------------------------------------------------------------- */
+impl From<&Counter> for JsonValue {
+    fn from(value: &Counter) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+        map.insert("part".into(), JsonValue::Number(value.part.into()));
+        map.insert("name".into(), JsonValue::String(value.name.clone()));
+        map.insert("value".into(), u64_to_json(value.value));
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Counter {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected counter to be a JSON object.")?;
+
+        Ok(Counter {
+            part: json
+                .get("part")
+                .and_then(json_to_u64)
+                .and_then(|v| u8::try_from(v).ok())
+                .ok_or("Expected counter.part to be a part number.")?,
+            name: json
+                .get("name")
+                .and_then(|v| v.get::<String>())
+                .cloned()
+                .ok_or("Expected counter.name to be a string.")?,
+            value: json
+                .get("value")
+                .and_then(json_to_u64)
+                .ok_or("Expected counter.value to be an integer.")?,
+        })
+    }
+}
 
 impl From<&Timing> for JsonValue {
     fn from(value: &Timing) -> Self {
@@ -114,23 +611,37 @@ impl From<&Timing> for JsonValue {
         map.insert("day".into(), JsonValue::String(value.day.to_string()));
         map.insert("total_nanos".into(), JsonValue::Number(value.total_nanos));
 
-        let part_1 = value.part_1.clone().map(JsonValue::String);
-        let part_2 = value.part_2.clone().map(JsonValue::String);
-
+        map.insert(
+            "parse".into(),
+            opt_to_json(value.parse.as_ref(), JsonValue::from),
+        );
         map.insert(
             "part_1".into(),
-            match part_1 {
-                Some(x) => x,
-                None => JsonValue::Null,
-            },
+            opt_to_json(value.part_1.as_ref(), JsonValue::from),
         );
-
         map.insert(
             "part_2".into(),
-            match part_2 {
-                Some(x) => x,
-                None => JsonValue::Null,
-            },
+            opt_to_json(value.part_2.as_ref(), JsonValue::from),
+        );
+        map.insert(
+            "peak_memory".into(),
+            opt_to_json(value.peak_memory, u64_to_json),
+        );
+        map.insert(
+            "recorded_at".into(),
+            opt_to_json(value.recorded_at, u64_to_json),
+        );
+        map.insert(
+            "strategy".into(),
+            opt_to_json(value.strategy, |s| JsonValue::String(s.to_string())),
+        );
+        map.insert(
+            "fingerprint".into(),
+            opt_to_json(value.fingerprint.as_ref(), JsonValue::from),
+        );
+        map.insert(
+            "counters".into(),
+            JsonValue::Array(value.counters.iter().map(JsonValue::from).collect()),
         );
 
         JsonValue::Object(map)
@@ -151,15 +662,25 @@ impl TryFrom<&JsonValue> for Timing {
             .and_then(|day| Day::from_str(day).ok())
             .ok_or("Expected timing.day to be a Day struct.")?;
 
-        let part_1 = json
-            .get("part_1")
-            .map(|v| if v.is_null() { None } else { v.get::<String>() })
-            .ok_or("Expected timing.part_1 to be null or string.")?;
+        let get_part = |key: &str| -> Result<Option<PartTiming>, String> {
+            match json.get(key) {
+                None => Ok(None),
+                Some(v) if v.is_null() => Ok(None),
+                Some(v) => PartTiming::try_from(v)
+                    .map(Some)
+                    .map_err(|e| format!("day {day}, {key}: {e}")),
+            }
+        };
 
-        let part_2 = json
-            .get("part_2")
-            .map(|v| if v.is_null() { None } else { v.get::<String>() })
-            .ok_or("Expected timing.part_2 to be null or string.")?;
+        let get_u64 = |key: &str| -> Result<Option<u64>, String> {
+            match json.get(key) {
+                None => Ok(None),
+                Some(v) if v.is_null() => Ok(None),
+                Some(v) => json_to_u64(v)
+                    .map(Some)
+                    .ok_or(format!("Expected timing.{key} to be null or an integer.")),
+            }
+        };
 
         let total_nanos = json
             .get("total_nanos")
@@ -168,8 +689,127 @@ impl TryFrom<&JsonValue> for Timing {
 
         Ok(Timing {
             day,
-            part_1: part_1.cloned(),
-            part_2: part_2.cloned(),
+            parse: get_part("parse")?,
+            part_1: get_part("part_1")?,
+            part_2: get_part("part_2")?,
+            peak_memory: get_u64("peak_memory")?,
+            recorded_at: get_u64("recorded_at")?,
+            strategy: match json.get("strategy") {
+                None => {
+                    return Err("Expected timing.strategy to be null or a merge strategy.".into())
+                }
+                Some(v) if v.is_null() => None,
+                Some(v) => Some(
+                    v.get::<String>()
+                        .and_then(|s| MergeStrategy::from_str(s).ok())
+                        .ok_or("Expected timing.strategy to be null or a merge strategy.")?,
+                ),
+            },
+            fingerprint: match json.get("fingerprint") {
+                None => return Err("Expected timing.fingerprint to be null or an object.".into()),
+                Some(v) if v.is_null() => None,
+                Some(v) => Some(Fingerprint::try_from(v)?),
+            },
+            counters: json
+                .get("counters")
+                .and_then(|v| v.get::<Vec<JsonValue>>())
+                .ok_or("Expected timing.counters to be an array.")?
+                .iter()
+                .map(Counter::try_from)
+                .collect::<Result<_, _>>()?,
+            total_nanos,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Conversions from older schema versions to the current one.
+mod migrations {
+    use std::collections::HashMap;
+    use std::str::FromStr;
+    use tinyjson::JsonValue;
+
+    use super::{json_to_u64, parse_duration, PartTiming, Timing};
+    use crate::template::Day;
+
+    /// Version 2 did not store cold times, allocations, merge strategies, fingerprints and counters,
+    /// though builds that added them kept writing version 2. Missing keys are filled in as empty.
+    pub fn timing_from_v2(value: &JsonValue) -> Result<Timing, String> {
+        let mut json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected timing to be a JSON object.")?
+            .clone();
+
+        for key in ["parse", "part_1", "part_2"] {
+            if let Some(JsonValue::Object(part)) = json.get_mut(key) {
+                for key in ["cold_nanos", "allocations"] {
+                    part.entry(key.into()).or_insert(JsonValue::Null);
+                }
+            }
+        }
+        for key in ["strategy", "fingerprint"] {
+            json.entry(key.into()).or_insert(JsonValue::Null);
+        }
+        if json.get("counters").is_none_or(JsonValue::is_null) {
+            json.insert("counters".into(), JsonValue::Array(vec![]));
+        }
+
+        Timing::try_from(&JsonValue::Object(json))
+    }
+
+    /// Version 1 stored parts as display strings and had no sample counts.
+    pub fn timing_from_v1(value: &JsonValue) -> Result<Timing, String> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected timing to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected timing.day to be a Day struct.")?;
+
+        let get_part = |key: &str, required: bool| -> Result<Option<PartTiming>, String> {
+            let samples = json
+                .get(&format!("{key}_samples"))
+                .and_then(json_to_u64)
+                .unwrap_or(0);
+
+            match json.get(key) {
+                None if !required => Ok(None),
+                Some(v) if v.is_null() => Ok(None),
+                Some(v) => v
+                    .get::<String>()
+                    .and_then(|s| parse_duration(s))
+                    .map(|nanos| {
+                        Some(PartTiming {
+                            nanos,
+                            samples,
+                            cold_nanos: None,
+                            allocations: None,
+                        })
+                    })
+                    .ok_or(format!("Expected timing.{key} to be null or a duration.")),
+                None => Err(format!("Expected timing.{key} to be null or a duration.")),
+            }
+        };
+
+        let total_nanos = json
+            .get("total_nanos")
+            .and_then(|v| v.get::<f64>().copied())
+            .ok_or("Expected timing.total_nanos to be a number.")?;
+
+        Ok(Timing {
+            day,
+            parse: get_part("parse", false)?,
+            part_1: get_part("part_1", true)?,
+            part_2: get_part("part_2", true)?,
+            peak_memory: json.get("peak_memory").and_then(json_to_u64),
+            recorded_at: None,
+            strategy: None,
+            fingerprint: None,
+            counters: vec![],
             total_nanos,
         })
     }
@@ -181,72 +821,164 @@ impl TryFrom<&JsonValue> for Timing {
 mod tests {
     use crate::day;
 
-    use super::{Timing, Timings};
+    use super::{PartTiming, Timing, Timings};
+
+    pub fn part(millis: f64) -> Option<PartTiming> {
+        Some(PartTiming {
+            nanos: millis * 1_000_000_f64,
+            samples: 10,
+            cold_nanos: None,
+            allocations: None,
+        })
+    }
 
     fn get_mock_timings() -> Timings {
         Timings {
             data: vec![
                 Timing {
                     day: day!(1),
-                    part_1: Some("10ms".into()),
-                    part_2: Some("20ms".into()),
+                    part_1: part(10.0),
+                    part_2: part(20.0),
                     total_nanos: 3e+10,
+                    ..Timing::new(day!(1))
                 },
                 Timing {
                     day: day!(2),
-                    part_1: Some("30ms".into()),
-                    part_2: Some("40ms".into()),
+                    part_1: part(30.0),
+                    part_2: part(40.0),
                     total_nanos: 7e+10,
+                    ..Timing::new(day!(2))
                 },
                 Timing {
                     day: day!(4),
-                    part_1: Some("40ms".into()),
+                    part_1: part(40.0),
                     part_2: None,
                     total_nanos: 4e+10,
+                    ..Timing::new(day!(4))
                 },
             ],
         }
     }
 
     mod deserialization {
-        use crate::{day, template::timings::Timings};
+        use crate::{
+            day,
+            template::timings::{Error, MergeStrategy, PartTiming, Timings},
+        };
 
         #[test]
         fn handles_json_timings() {
-            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000000 }] }"#.to_string();
-            let timings = Timings::try_from(json).unwrap();
+            let json = r#"{ "version": 3, "data": [{ "day": "01", "parse": null, "part_1": { "nanos": 1000000, "samples": 100, "cold_nanos": null, "allocations": null }, "part_2": null, "peak_memory": 2048, "recorded_at": 1733029920, "strategy": null, "fingerprint": null, "counters": [], "total_nanos": 1000000 }] }"#;
+            let timings = Timings::from_json(json).unwrap();
+            assert_eq!(timings.data.len(), 1);
+            let timing = timings.data.first().unwrap();
+            assert_eq!(timing.day, day!(1));
+            assert_eq!(
+                timing.part_1,
+                Some(PartTiming {
+                    nanos: 1_000_000_f64,
+                    samples: 100,
+                    cold_nanos: None,
+                    allocations: None,
+                })
+            );
+            assert_eq!(timing.part_2, None);
+            assert_eq!(timing.peak_memory, Some(2048));
+            assert_eq!(timing.recorded_at, Some(1_733_029_920));
+            assert_eq!(timing.total_nanos, 1_000_000_f64);
+        }
+
+        #[test]
+        fn migrates_version_2_timings() {
+            let json = r#"{ "version": 2, "data": [{ "day": "01", "parse": null, "part_1": { "nanos": 1000000, "samples": 100 }, "part_2": { "nanos": 5, "samples": 1, "cold_nanos": 9 }, "peak_memory": 2048, "recorded_at": 1733029920, "strategy": "min", "total_nanos": 1000000 }] }"#;
+            let timing = Timings::from_json(json).unwrap().data.remove(0);
+            assert_eq!(timing.part_1.unwrap().samples, 100);
+            assert_eq!(timing.part_1.unwrap().cold_nanos, None);
+            assert_eq!(timing.part_2.unwrap().cold_nanos, Some(9_f64));
+            assert_eq!(timing.peak_memory, Some(2048));
+            assert_eq!(timing.strategy, Some(MergeStrategy::Minimum));
+            assert_eq!(timing.fingerprint, None);
+            assert_eq!(timing.counters, vec![]);
+        }
+
+        #[test]
+        fn rejects_incomplete_timings() {
+            let json = r#"{ "version": 3, "data": [{ "day": "01", "part_1": null, "part_2": null, "total_nanos": 0 }] }"#;
+            let err = Timings::from_json(json).unwrap_err();
+            assert!(matches!(err, Error::Corrupt(reason) if reason.contains("strategy")));
+        }
+
+        #[test]
+        fn migrates_legacy_timings() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000000 }] }"#;
+            let timings = Timings::from_json(json).unwrap();
             assert_eq!(timings.data.len(), 1);
             let timing = timings.data.first().unwrap();
             assert_eq!(timing.day, day!(1));
-            assert_eq!(timing.part_1, Some("1ms".to_string()));
+            assert_eq!(
+                timing.part_1,
+                Some(PartTiming {
+                    nanos: 1_000_000_f64,
+                    samples: 0,
+                    cold_nanos: None,
+                    allocations: None,
+                })
+            );
             assert_eq!(timing.part_2, None);
+            assert_eq!(timing.recorded_at, None);
             assert_eq!(timing.total_nanos, 1_000_000_000_f64);
         }
 
+        #[test]
+        fn migrates_legacy_sample_counts() {
+            let json = r#"{ "data": [{ "day": "03", "parse": "2µs", "part_1": "1ms", "part_1_samples": 50, "part_2": "74.13ns", "total_nanos": 1002074.13 }] }"#;
+            let timing = Timings::from_json(json).unwrap().data.remove(0);
+            assert_eq!(timing.parse.unwrap().nanos, 2000_f64);
+            assert_eq!(timing.part_1.unwrap().samples, 50);
+            assert_eq!(timing.part_2.unwrap().nanos, 74.13_f64);
+        }
+
         #[test]
         fn handles_empty_timings() {
-            let json = r#"{ "data": [] }"#.to_string();
-            let timings = Timings::try_from(json).unwrap();
+            let json = r#"{ "data": [] }"#;
+            let timings = Timings::from_json(json).unwrap();
             assert_eq!(timings.data.len(), 0);
         }
 
         #[test]
         #[should_panic]
         fn panics_for_invalid_json() {
-            let json = r#"{}"#.to_string();
-            Timings::try_from(json).unwrap();
+            let json = r#"{}"#;
+            Timings::from_json(json).unwrap();
         }
 
         #[test]
         #[should_panic]
         fn panics_for_malformed_timings() {
-            let json = r#"{ "data": [{ "day": "01" }, { "day": "26" }, { "day": "02", "part_2": null, "total_nanos": 0 }] }"#.to_string();
-            Timings::try_from(json).unwrap();
+            let json = r#"{ "data": [{ "day": "01" }, { "day": "26" }, { "day": "02", "part_2": null, "total_nanos": 0 }] }"#;
+            Timings::from_json(json).unwrap();
+        }
+
+        #[test]
+        fn reports_malformed_parts() {
+            let json = r#"{ "version": 2, "data": [{ "day": "05", "part_1": { "nanos": "fast" }, "total_nanos": 0 }] }"#;
+            let err = Timings::from_json(json).unwrap_err();
+            assert!(matches!(err, Error::Corrupt(reason) if reason.contains("day 05")));
+        }
+
+        #[test]
+        fn reports_unsupported_versions() {
+            let json = r#"{ "version": 99, "data": [] }"#;
+            assert!(matches!(
+                Timings::from_json(json),
+                Err(Error::UnsupportedVersion(99))
+            ));
         }
     }
 
     mod serialization {
         use super::get_mock_timings;
+        use crate::template::timings::{Timings, SCHEMA_VERSION};
         use std::collections::HashMap;
         use tinyjson::JsonValue;
 
@@ -254,10 +986,9 @@ mod tests {
         fn serializes_timings() {
             let timings = get_mock_timings();
             let value = JsonValue::try_from(timings).unwrap();
+            let document = value.get::<HashMap<String, JsonValue>>().unwrap();
             assert_eq!(
-                value
-                    .get::<HashMap<String, JsonValue>>()
-                    .unwrap()
+                document
                     .get("data")
                     .unwrap()
                     .get::<Vec<JsonValue>>()
@@ -265,6 +996,38 @@ mod tests {
                     .len(),
                 3
             );
+            assert_eq!(
+                document.get("version").unwrap().get::<f64>(),
+                Some(&f64::from(SCHEMA_VERSION))
+            );
+        }
+
+        #[test]
+        fn round_trips_timings() {
+            let mut timings = get_mock_timings();
+            timings.data[0].strategy = Some(crate::template::timings::MergeStrategy::Median(7));
+            timings.data[0].part_1.as_mut().unwrap().cold_nanos = Some(2_500_000_f64);
+            timings.data[0].part_2.as_mut().unwrap().allocations =
+                Some(crate::template::alloc::Allocations {
+                    count: 3,
+                    bytes: 4096,
+                    peak: 2048,
+                });
+            timings.data[1].counters = vec![crate::template::timings::Counter {
+                part: 1,
+                name: "heap_pops".into(),
+                value: 12345,
+            }];
+            let json = JsonValue::from(timings.clone()).stringify().unwrap();
+            let parsed = Timings::from_json(&json).unwrap();
+            assert_eq!(parsed.data.len(), 3);
+            assert_eq!(parsed.data[0].part_1, timings.data[0].part_1);
+            assert_eq!(parsed.data[0].part_2, timings.data[0].part_2);
+            assert_eq!(parsed.data[2].part_2, None);
+            assert_eq!(parsed.data[0].strategy, timings.data[0].strategy);
+            assert_eq!(parsed.data[1].strategy, None);
+            assert_eq!(parsed.data[0].counters, vec![]);
+            assert_eq!(parsed.data[1].counters, timings.data[1].counters);
         }
     }
 
@@ -274,18 +1037,21 @@ mod tests {
             template::timings::{Timing, Timings},
         };
 
+        use super::part;
+
         #[test]
         fn handles_completed_days() {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
-                    part_1: Some("1ms".into()),
-                    part_2: Some("2ms".into()),
+                    part_1: part(1.0),
+                    part_2: part(2.0),
                     total_nanos: 3_000_000_000_f64,
+                    ..Timing::new(day!(1))
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(day!(1)), true);
         }
 
         #[test]
@@ -293,13 +1059,14 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
-                    part_1: Some("1ms".into()),
+                    part_1: part(1.0),
                     part_2: None,
                     total_nanos: 1_000_000_000_f64,
+                    ..Timing::new(day!(1))
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
 
         #[test]
@@ -310,20 +1077,146 @@ mod tests {
                     part_1: None,
                     part_2: None,
                     total_nanos: 0.0,
+                    ..Timing::new(day!(1))
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
     }
 
     mod merge {
         use crate::{
             day,
-            template::timings::{Timing, Timings},
+            template::timings::{MergeStrategy, PartTiming, Timing, Timings},
         };
 
-        use super::get_mock_timings;
+        use super::{get_mock_timings, part};
+
+        fn run(day_num: u8, p1_millis: f64, p2_millis: Option<f64>) -> Timing {
+            let mut timing = Timing::new(crate::template::Day::new(day_num).unwrap());
+            timing.part_1 = part(p1_millis);
+            timing.part_2 = p2_millis.and_then(part);
+            timing.update_total();
+            timing
+        }
+
+        #[test]
+        fn parses_strategies() {
+            assert_eq!("latest".parse(), Ok(MergeStrategy::Latest));
+            assert_eq!("min".parse(), Ok(MergeStrategy::Minimum));
+            assert_eq!("median".parse(), Ok(MergeStrategy::Median(5)));
+            assert_eq!("median:3".parse(), Ok(MergeStrategy::Median(3)));
+            assert!("median:0".parse::<MergeStrategy>().is_err());
+            assert!("best".parse::<MergeStrategy>().is_err());
+        }
+
+        #[test]
+        fn keeps_minimum_per_part() {
+            let stored = Timings {
+                data: vec![run(1, 5.0, Some(50.0))],
+            };
+            let new = Timings {
+                data: vec![run(1, 10.0, Some(20.0))],
+            };
+            let merged = stored.merge_with(&new, MergeStrategy::Minimum, &Timings::default());
+
+            assert_eq!(merged.data.len(), 1);
+            assert_eq!(merged.data[0].part_1_nanos(), Some(5_000_000_f64));
+            assert_eq!(merged.data[0].part_2_nanos(), Some(20_000_000_f64));
+            assert_eq!(merged.data[0].total_nanos, 25_000_000_f64);
+            assert_eq!(merged.data[0].strategy, Some(MergeStrategy::Minimum));
+        }
+
+        #[test]
+        fn keeps_rolling_median() {
+            let history = Timings {
+                data: vec![
+                    run(1, 1.0, Some(1.0)),
+                    run(2, 99.0, None),
+                    run(1, 30.0, Some(3.0)),
+                    run(1, 10.0, Some(9.0)),
+                    run(1, 20.0, Some(4.0)),
+                ],
+            };
+            let new = Timings {
+                data: vec![run(1, 20.0, Some(4.0))],
+            };
+            let merged = Timings::default().merge_with(&new, MergeStrategy::Median(3), &history);
+
+            // window covers the last three runs of day 1: 30/3, 10/9 and 20/4.
+            assert_eq!(merged.data[0].part_1_nanos(), Some(20_000_000_f64));
+            assert_eq!(merged.data[0].part_2_nanos(), Some(4_000_000_f64));
+            assert_eq!(merged.data[0].strategy, Some(MergeStrategy::Median(3)));
+        }
+
+        #[test]
+        fn only_merges_comparable_runs() {
+            let fingerprint = crate::template::fingerprint::Fingerprint {
+                cpu: "Apple M1".into(),
+                cores: 8,
+                os: "macos aarch64".into(),
+                rustc: "rustc 1.83.0".into(),
+                profile: "release".into(),
+                on_battery: None,
+                commit: None,
+            };
+
+            let stored = Timings {
+                data: vec![run(1, 5.0, Some(5.0))],
+            };
+            let mut new_run = run(1, 10.0, Some(20.0));
+            new_run.fingerprint = Some(fingerprint);
+            let new = Timings {
+                data: vec![new_run.clone()],
+            };
+
+            let merged = stored.merge_with(&new, MergeStrategy::Minimum, &Timings::default());
+            assert_eq!(merged.data[0].part_1_nanos(), Some(10_000_000_f64));
+
+            let history = Timings {
+                data: vec![run(1, 1.0, None), run(1, 2.0, None), new_run],
+            };
+            let merged = stored.merge_with(&new, MergeStrategy::Median(3), &history);
+            assert_eq!(merged.data[0].part_1_nanos(), Some(10_000_000_f64));
+        }
+
+        #[test]
+        fn caps_history() {
+            let mut history = Timings::default();
+            for i in 0..60 {
+                history.append_history(&Timings {
+                    data: vec![run(1, f64::from(i), None), run(2, 1.0, None)],
+                });
+            }
+
+            let day_1: Vec<&Timing> = history.data.iter().filter(|t| t.day == day!(1)).collect();
+            assert_eq!(day_1.len(), 50);
+            assert_eq!(day_1[0].part_1_nanos(), Some(10_000_000_f64));
+            assert_eq!(history.data.len(), 100);
+        }
+
+        #[test]
+        fn keeps_latest() {
+            let stored = get_mock_timings();
+            let new = Timings {
+                data: vec![run(2, 99.0, None)],
+            };
+            let merged = stored.merge_with(&new, MergeStrategy::Latest, &Timings::default());
+
+            assert_eq!(merged.data.len(), 3);
+            assert_eq!(
+                merged.data[1].part_1,
+                Some(PartTiming {
+                    nanos: 99_000_000_f64,
+                    samples: 10,
+                    cold_nanos: None,
+                    allocations: None,
+                })
+            );
+            assert_eq!(merged.data[1].part_2, None);
+            assert_eq!(merged.data[1].strategy, Some(MergeStrategy::Latest));
+        }
 
         #[test]
         fn handles_disjunct_timings() {
@@ -334,6 +1227,7 @@ mod tests {
                     part_1: None,
                     part_2: None,
                     total_nanos: 0_f64,
+                    ..Timing::new(day!(3))
                 }],
             };
             let merged = timings.merge(&other);
@@ -354,6 +1248,7 @@ mod tests {
                     part_1: None,
                     part_2: None,
                     total_nanos: 0_f64,
+                    ..Timing::new(day!(2))
                 }],
             };
             let merged = timings.merge(&other);
@@ -0,0 +1,300 @@
+/// Module that checks solutions against known answers, see `cargo verify`.
+use std::{
+    ffi::OsStr,
+    fmt::Display,
+    path::{Path, PathBuf},
+};
+
+use crate::template::answers::Answers;
+use crate::template::config;
+use crate::template::corpus::{Corpus, KnownAnswers};
+use crate::template::run_multi::{get_path_for_bin, run_bin};
+use crate::template::{Day, ANSI_BOLD, ANSI_RESET};
+
+/// Result of one part of a solution on one input.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Outcome {
+    Answer(String),
+    Unsolved,
+    /// The solution binary exited before printing the part, e.g. because it panicked.
+    Crashed,
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Outcome::Answer(answer) => write!(f, "{answer}"),
+            Outcome::Unsolved => write!(f, "✖"),
+            Outcome::Crashed => write!(f, "crashed"),
+        }
+    }
+}
+
+/// Input of a day together with its known answers.
+struct Input {
+    name: String,
+    path: PathBuf,
+    known: KnownAnswers,
+}
+
+/// Outcome of the verification of one input.
+enum Verdict {
+    Passed,
+    Failed(Vec<String>),
+    /// None of the answers of the input are known.
+    Unknown,
+}
+
+/// Runs the solutions of `days` on their inputs and compares the answers to the known ones.
+/// Returns the names of the inputs each failing day failed on.
+pub fn verify(days: &[Day], with_corpus: bool) -> Result<Vec<(Day, Vec<String>)>, String> {
+    let answers = Answers::read_from_file();
+    let corpus = if with_corpus {
+        Corpus::read_from_dir()?
+    } else {
+        Corpus::default()
+    };
+
+    let mut failures = vec![];
+    let mut counts = (0, 0, 0);
+    let mut need_space = false;
+
+    for &day in days {
+        if !Path::new(&get_path_for_bin(day)).exists() {
+            continue;
+        }
+
+        let inputs = collect_inputs(day, &answers, &corpus);
+        if inputs.is_empty() {
+            continue;
+        }
+
+        if need_space {
+            println!();
+        }
+        need_space = true;
+
+        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+        println!("------");
+
+        let mut failed_on = vec![];
+
+        for input in &inputs {
+            let outcomes = run_solution(day, &input.path)?;
+            let results = format!("{}, {}", outcomes[0], outcomes[1]);
+
+            match check(&outcomes, &input.known) {
+                Verdict::Passed => {
+                    counts.0 += 1;
+                    println!("  ✔ {}: {results}", input.name);
+                }
+                Verdict::Failed(mismatches) => {
+                    counts.1 += 1;
+                    println!("  ✖ {}: {}", input.name, mismatches.join(", "));
+                    failed_on.push(input.name.clone());
+                }
+                Verdict::Unknown => {
+                    counts.2 += 1;
+                    println!("  ? {}: {results} (no known answers)", input.name);
+                }
+            }
+        }
+
+        if !failed_on.is_empty() {
+            failures.push((day, failed_on));
+        }
+    }
+
+    let (passed, failed, unknown) = counts;
+    println!(
+        "\n{ANSI_BOLD}Verified {} inputs:{ANSI_RESET} {passed} passed, {failed} failed, {unknown} without known answers.",
+        passed + failed + unknown
+    );
+
+    Ok(failures)
+}
+
+/// The day's own input, checked against the accepted answers, followed by the corpus variants.
+fn collect_inputs(day: Day, answers: &Answers, corpus: &Corpus) -> Vec<Input> {
+    let mut inputs = vec![];
+
+    let path = config::get().paths.inputs.join(format!("{day}.txt"));
+    if path.exists() {
+        let accepted = answers.get(day);
+        let value = |part: u8| {
+            let answer = accepted.and_then(|a| {
+                if part == 1 {
+                    a.part_1.as_ref()
+                } else {
+                    a.part_2.as_ref()
+                }
+            });
+            answer.map(|a| a.value.clone())
+        };
+
+        inputs.push(Input {
+            name: "own".into(),
+            path,
+            known: KnownAnswers {
+                part_1: value(1),
+                part_2: value(2),
+            },
+        });
+    }
+
+    inputs.extend(corpus.variants.iter().filter_map(|variant| {
+        Some(Input {
+            name: variant.name.clone(),
+            path: variant.input_path(day)?,
+            known: variant.answers(day),
+        })
+    }));
+
+    inputs
+}
+
+fn check(outcomes: &[Outcome; 2], known: &KnownAnswers) -> Verdict {
+    if known.part_1.is_none() && known.part_2.is_none() {
+        return Verdict::Unknown;
+    }
+
+    let mismatches: Vec<String> = [1, 2]
+        .into_iter()
+        .zip(outcomes)
+        .filter_map(|(part, outcome)| {
+            let expected = known.get(part)?;
+            match outcome {
+                Outcome::Answer(answer) if answer == expected => None,
+                _ => Some(format!("part {part} expected {expected}, got {outcome}")),
+            }
+        })
+        .collect();
+
+    if mismatches.is_empty() {
+        Verdict::Passed
+    } else {
+        Verdict::Failed(mismatches)
+    }
+}
+
+/// Runs the solution binary of `day` on the input at `path`.
+fn run_solution(day: Day, path: &Path) -> Result<[Outcome; 2], String> {
+    let output = run_bin(day, [OsStr::new("--input"), path.as_os_str()])
+        .output()
+        .map_err(|e| format!("Failed to run solution of day {day}: {e}"))?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<String> = stdout.lines().map(str::to_string).collect();
+    let [part_1, part_2] = parse_results(&lines);
+
+    let missing = if output.status.success() {
+        Outcome::Unsolved
+    } else {
+        Outcome::Crashed
+    };
+
+    Ok([
+        part_1.unwrap_or_else(|| missing.clone()),
+        part_2.unwrap_or(missing),
+    ])
+}
+
+/// Parses the results printed by `run_part`, e.g. `Part 1: 42 (1.2ms)`.
+/// Multi-line results are printed below a `Part 1: ▼` line.
+fn parse_results(output: &[String]) -> [Option<Outcome>; 2] {
+    let mut results = [None, None];
+    let mut lines = output.iter().peekable();
+
+    while let Some(line) = lines.next() {
+        // intermediate results are overwritten with a carriage return.
+        let line = line.rsplit('\r').next().unwrap_or_default();
+
+        let Some((part, result)) = line
+            .strip_prefix("Part 1: ")
+            .map(|r| (0, r))
+            .or_else(|| line.strip_prefix("Part 2: ").map(|r| (1, r)))
+        else {
+            continue;
+        };
+
+        let outcome = if result.starts_with('✖') {
+            Outcome::Unsolved
+        } else if result.starts_with('▼') {
+            let mut answer = vec![];
+            while let Some(next) =
+                lines.next_if(|l| !l.starts_with("Part ") && !l.starts_with("  "))
+            {
+                answer.push(next.as_str());
+            }
+            Outcome::Answer(answer.join("\n"))
+        } else {
+            let answer = result
+                .strip_prefix(ANSI_BOLD)
+                .and_then(|r| r.split_once(ANSI_RESET))
+                .map_or(result, |(answer, _)| answer);
+            Outcome::Answer(answer.to_string())
+        };
+
+        results[part] = Some(outcome);
+    }
+
+    results
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{check, parse_results, Outcome, Verdict};
+    use crate::template::corpus::KnownAnswers;
+
+    #[test]
+    fn parses_results() {
+        let output: Vec<String> = [
+            "Part 1: \x1b[1m11\x1b[0m\rPart 1: \x1b[1m11\x1b[0m (12.0µs)",
+            "  counters: memo_hits=3",
+            "Part 2: ▼ \rPart 2: ▼  (1.0ms)",
+            "#.#",
+            ".#.",
+        ]
+        .into_iter()
+        .map(String::from)
+        .collect();
+
+        assert_eq!(
+            parse_results(&output),
+            [
+                Some(Outcome::Answer("11".into())),
+                Some(Outcome::Answer("#.#\n.#.".into()))
+            ]
+        );
+
+        let output = vec!["Part 1: ✖             ".to_string()];
+        assert_eq!(parse_results(&output), [Some(Outcome::Unsolved), None]);
+    }
+
+    #[test]
+    fn checks_known_answers() {
+        let known = KnownAnswers {
+            part_1: Some("11".into()),
+            part_2: None,
+        };
+
+        let outcomes = [Outcome::Answer("11".into()), Outcome::Unsolved];
+        assert!(matches!(check(&outcomes, &known), Verdict::Passed));
+
+        let outcomes = [Outcome::Crashed, Outcome::Unsolved];
+        match check(&outcomes, &known) {
+            Verdict::Failed(mismatches) => {
+                assert_eq!(mismatches, vec!["part 1 expected 11, got crashed"]);
+            }
+            _ => panic!("expected a failed verdict"),
+        }
+
+        let outcomes = [Outcome::Unsolved, Outcome::Unsolved];
+        assert!(matches!(
+            check(&outcomes, &KnownAnswers::default()),
+            Verdict::Unknown
+        ));
+    }
+}
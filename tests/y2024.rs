@@ -0,0 +1,14 @@
+//! Calls the solutions through the library, as another crate would.
+use advent_of_code::template::read_file;
+use advent_of_code::template::solution::Outcome;
+use advent_of_code::y2024::day05;
+
+#[test]
+fn solves_through_the_library() {
+    let input = read_file("examples", day05::DAY);
+    assert_eq!(day05::part_one(&input), Some(143));
+    assert_eq!(
+        day05::solve(&input),
+        (Outcome::Solved(143), Outcome::Solved(123))
+    );
+}